@@ -0,0 +1,106 @@
+//! The local directory the attachment tools may read and write.
+//!
+//! `issue_attach_file` with a `path` and `attachment_download` touch the
+//! filesystem of the machine the server runs on. Without a configured
+//! directory both refuse local paths, so a client (possibly remote, over the
+//! HTTP transport) cannot upload credentials or overwrite arbitrary files.
+//! With one, paths are resolved against it and symlinks are followed before
+//! checking that the file is inside; downloads never write through a symlink.
+
+use crate::error::JiraError;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Clone, Debug)]
+pub struct AttachmentsDir {
+    /// Canonical path of the directory.
+    root: PathBuf,
+}
+
+impl AttachmentsDir {
+    /// Creates `path` if needed.
+    pub fn new(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::create_dir_all(path.as_ref())?;
+        Ok(Self {
+            root: path.as_ref().canonicalize()?,
+        })
+    }
+
+    /// Reads `JIRA_ATTACHMENTS_DIR`; unset leaves local paths disabled.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        match std::env::var("JIRA_ATTACHMENTS_DIR") {
+            Ok(v) if !v.trim().is_empty() => Self::new(v.trim()).map(Some).map_err(|e| {
+                anyhow::anyhow!("Cannot use JIRA_ATTACHMENTS_DIR '{}': {}", v.trim(), e)
+            }),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The existing file `path` (relative to the directory, or absolute)
+    /// when it is inside the directory.
+    pub fn readable(&self, path: &str) -> Result<PathBuf, JiraError> {
+        let resolved = self
+            .root
+            .join(path)
+            .canonicalize()
+            .map_err(|e| JiraError::validation(format!("Cannot read '{}': {}", path, e)))?;
+        self.check(path, &resolved)?;
+        if !resolved.is_file() {
+            return Err(JiraError::validation(format!("'{}' is not a file", path)));
+        }
+        Ok(resolved)
+    }
+
+    /// Where to write `path` (relative to the directory, or absolute); an
+    /// existing directory gets `file_name` appended. The parent directory
+    /// must exist inside the attachments directory.
+    pub fn writable(&self, path: &str, file_name: &str) -> Result<PathBuf, JiraError> {
+        let mut target = self.root.join(path);
+        if target.is_dir() {
+            let name = Path::new(file_name).file_name().ok_or_else(|| {
+                JiraError::validation(format!("'{}' is not a valid file name", file_name))
+            })?;
+            target.push(name);
+        }
+        let name = match target.components().next_back() {
+            Some(Component::Normal(name)) => name.to_os_string(),
+            _ => {
+                return Err(JiraError::validation(format!(
+                    "'{}' does not name a file",
+                    path
+                )));
+            }
+        };
+        let parent = target
+            .parent()
+            .unwrap_or(&self.root)
+            .canonicalize()
+            .map_err(|e| JiraError::validation(format!("Cannot write '{}': {}", path, e)))?;
+        let resolved = parent.join(name);
+        self.check(path, &resolved)?;
+        // A symlink, even a dangling one, would be followed by the write and
+        // could create or replace a file outside the directory
+        if std::fs::symlink_metadata(&resolved).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(JiraError::validation(format!(
+                "'{}' is a symbolic link; attachments are not written through links",
+                path
+            )));
+        }
+        Ok(resolved)
+    }
+
+    fn check(&self, path: &str, resolved: &Path) -> Result<(), JiraError> {
+        if resolved.starts_with(&self.root) {
+            Ok(())
+        } else {
+            Err(JiraError::validation(format!(
+                "'{}' is outside the attachments directory {}",
+                path,
+                self.root.display()
+            )))
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::site::Method;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+/// One mutating request sent to Jira, as stored in the audit log.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// RFC 3339 time the response (or transport error) was received.
+    pub timestamp: String,
+    pub tool: String,
+    pub arguments: Value,
+    /// Issue keys found in the arguments and the request URL.
+    pub issue_keys: Vec<String>,
+    pub method: String,
+    pub url: String,
+    /// HTTP status of the response; absent when the request never got one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Criteria of [`AuditLog::query`]; empty fields match everything.
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    pub tool: Option<String>,
+    pub issue_key: Option<String>,
+    /// RFC 3339 timestamp; older entries are skipped.
+    pub since: Option<String>,
+    pub limit: usize,
+}
+
+/// Append-only JSONL file recording every non-GET request made on behalf of
+/// a tool call.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    /// Serializes appends so concurrent tool calls never interleave lines.
+    lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Reads `JIRA_AUDIT_LOG`: a file path, or `off` to disable auditing.
+    /// Defaults to `audit.jsonl` in the jira-rs config directory.
+    pub fn from_env() -> Option<Self> {
+        match std::env::var("JIRA_AUDIT_LOG") {
+            Ok(v) if matches!(v.trim().to_lowercase().as_str(), "off" | "false" | "0") => None,
+            Ok(v) if !v.trim().is_empty() => Some(Self::new(v.trim())),
+            _ => Some(Self::new(crate::config::config_dir().join("audit.jsonl"))),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `entry`. Failures are logged rather than returned: losing an
+    /// audit line must not turn a successful Jira change into a tool error.
+    pub async fn append(&self, entry: &AuditEntry) {
+        let _guard = self.lock.lock().await;
+        let result = async {
+            if let Some(parent) = self.path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            let mut line = serde_json::to_vec(entry)?;
+            line.push(b'\n');
+            file.write_all(&line).await?;
+            file.flush().await
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Could not write audit log {}: {}", self.path.display(), e);
+        }
+    }
+
+    /// Most recent entries matching `filter`, newest first. Unparseable lines
+    /// are skipped.
+    pub async fn query(&self, filter: &AuditFilter) -> std::io::Result<Vec<AuditEntry>> {
+        let file = match tokio::fs::File::open(&self.path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let since = filter
+            .since
+            .as_deref()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+        let matches = |e: &AuditEntry| {
+            filter.tool.as_ref().is_none_or(|t| e.tool == *t)
+                && filter
+                    .issue_key
+                    .as_ref()
+                    .is_none_or(|k| e.issue_keys.iter().any(|key| key.eq_ignore_ascii_case(k)))
+                && since.is_none_or(|since| {
+                    chrono::DateTime::parse_from_rfc3339(&e.timestamp)
+                        .is_ok_and(|timestamp| timestamp >= since)
+                })
+        };
+
+        // Only the newest `limit` matches are kept while reading
+        let mut entries = VecDeque::new();
+        let mut lines = tokio::io::BufReader::new(file).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+                continue;
+            };
+            if matches(&entry) {
+                entries.push_back(entry);
+                if entries.len() > filter.limit {
+                    entries.pop_front();
+                }
+            }
+        }
+        Ok(entries.into_iter().rev().collect())
+    }
+}
+
+/// Longest string argument stored verbatim; longer ones are summarized.
+const MAX_LOGGED_STRING: usize = 1024;
+
+/// The tool call being served, made available to `Site::send_request`.
+#[derive(Clone, Debug)]
+pub struct ToolCall {
+    pub log: Arc<AuditLog>,
+    pub tool: String,
+    pub arguments: Value,
+}
+
+tokio::task_local! {
+    static CURRENT: ToolCall;
+}
+
+impl ToolCall {
+    /// Audit context of a call to `tool`. Base64 content and oversized
+    /// strings in `arguments` are replaced by a summary so uploads do not
+    /// end up in the log.
+    pub fn new(log: Arc<AuditLog>, tool: impl Into<String>, arguments: Value) -> Self {
+        Self {
+            log,
+            tool: tool.into(),
+            arguments: summarize(arguments, None),
+        }
+    }
+
+    /// Runs `future` (the tool) with this call as the audit context.
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+}
+
+/// Records a request made by the current tool call. GET requests and
+/// requests outside a tool call are not audited.
+pub(crate) async fn record(method: Method, url: &str, status: Option<u16>, error: Option<String>) {
+    if matches!(method, Method::Get) {
+        return;
+    }
+    let Ok(call) = CURRENT.try_with(ToolCall::clone) else {
+        return;
+    };
+    let mut issue_keys = Vec::new();
+    collect_issue_keys(&call.arguments, &mut issue_keys);
+    let path = url.split('?').next().unwrap_or(url);
+    for segment in path.split('/') {
+        if is_issue_key(segment) && !issue_keys.iter().any(|k| k == segment) {
+            issue_keys.push(segment.to_string());
+        }
+    }
+
+    call.log
+        .append(&AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            tool: call.tool,
+            arguments: call.arguments,
+            issue_keys,
+            method: method.as_str().to_string(),
+            url: url.to_string(),
+            status,
+            error,
+        })
+        .await;
+}
+
+/// Replaces the values of `*Base64` keys and strings longer than
+/// [`MAX_LOGGED_STRING`] with their size.
+fn summarize(value: Value, key: Option<&str>) -> Value {
+    match value {
+        Value::String(s) if key.is_some_and(|k| k.ends_with("Base64")) => {
+            let bytes = s.trim_end_matches('=').len() * 3 / 4;
+            Value::String(format!("<base64, {} bytes>", bytes))
+        }
+        Value::String(s) if s.len() > MAX_LOGGED_STRING => {
+            Value::String(format!("<text, {} bytes>", s.len()))
+        }
+        Value::Array(items) => items.into_iter().map(|v| summarize(v, None)).collect(),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let v = summarize(v, Some(&k));
+                    (k, v)
+                })
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn collect_issue_keys(value: &Value, keys: &mut Vec<String>) {
+    match value {
+        Value::String(s) if is_issue_key(s) && !keys.contains(s) => keys.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|v| collect_issue_keys(v, keys)),
+        Value::Object(map) => map.values().for_each(|v| collect_issue_keys(v, keys)),
+        _ => {}
+    }
+}
+
+/// `PROJ-123`: an uppercase project key, a dash and a number.
+fn is_issue_key(s: &str) -> bool {
+    let Some((project, number)) = s.rsplit_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
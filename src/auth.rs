@@ -0,0 +1,51 @@
+use crate::error::JiraError;
+use crate::oauth::OAuthSession;
+use std::fmt;
+use std::sync::Arc;
+
+/// How requests authenticate against Jira.
+#[derive(Clone)]
+pub enum Auth {
+    /// Email + API token on Cloud, username + password on Data Center.
+    Basic { username: String, password: String },
+    /// Personal Access Token (Data Center / Server).
+    Bearer(String),
+    /// OAuth 2.0 (3LO) access token, refreshed automatically.
+    OAuth2(Arc<OAuthSession>),
+}
+
+impl Auth {
+    pub async fn apply(
+        &self,
+        client: &reqwest::Client,
+        req: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, JiraError> {
+        Ok(match self {
+            Auth::Basic { username, password } => req.basic_auth(username, Some(password)),
+            Auth::Bearer(token) => req.bearer_auth(token),
+            Auth::OAuth2(session) => req.bearer_auth(session.access_token(client).await?),
+        })
+    }
+
+    /// Handles a 401 answer. Returns `true` when new credentials were obtained
+    /// and the request is worth replaying.
+    pub async fn refresh(&self, client: &reqwest::Client) -> Result<bool, JiraError> {
+        match self {
+            Auth::OAuth2(session) => session.refresh(client).await.map(|_| true),
+            _ => Ok(false),
+        }
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            Auth::Bearer(_) => f.write_str("Bearer(..)"),
+            Auth::OAuth2(_) => f.write_str("OAuth2(..)"),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Map whose entries expire `ttl` after being stored.
+#[derive(Debug)]
+pub struct TtlCache<V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, V)>>,
+}
+
+impl<V: Clone> TtlCache<V> {
+    /// Most entries kept; the oldest is dropped to make room.
+    pub const MAX_ENTRIES: usize = 1000;
+
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<V> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(stored, _)| stored.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn insert(&self, key: &str, value: V) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        // Expired entries are never read again; drop them so per-issue keys
+        // do not accumulate in a long-running server
+        entries.retain(|_, (stored, _)| stored.elapsed() < self.ttl);
+        if entries.len() >= Self::MAX_ENTRIES
+            && !entries.contains_key(key)
+            && let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (stored, _))| *stored)
+                .map(|(key, _)| key.clone())
+        {
+            entries.remove(&oldest);
+        }
+        entries.insert(key.to_string(), (Instant::now(), value));
+    }
+
+    pub fn remove(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(key);
+    }
+
+    /// Drops every entry and returns how many there were.
+    pub fn clear(&self) -> usize {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let count = entries.len();
+        entries.clear();
+        count
+    }
+}
+
+/// Jira metadata that rarely changes but is expensive to download:
+/// the field list, createmeta issue types per project and editmeta per issue.
+#[derive(Debug)]
+pub struct MetadataCache {
+    pub fields: TtlCache<Arc<Vec<serde_json::Value>>>,
+    pub createmeta: TtlCache<Arc<Vec<serde_json::Value>>>,
+    pub editmeta: TtlCache<Arc<serde_json::Value>>,
+}
+
+impl MetadataCache {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            fields: TtlCache::new(ttl),
+            createmeta: TtlCache::new(ttl),
+            editmeta: TtlCache::new(ttl),
+        }
+    }
+
+    /// Reads `JIRA_CACHE_TTL_SECS` (default 300, `0` disables caching).
+    pub fn ttl_from_env() -> Duration {
+        std::env::var("JIRA_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Self::DEFAULT_TTL)
+    }
+}
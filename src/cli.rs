@@ -0,0 +1,604 @@
+//! Command line front end: every MCP tool doubles as a subcommand.
+//!
+//! `jira-rs issue get PROJ-1 --filter basic` calls the `issue_get` tool with
+//! `{"issueKey": "PROJ-1", "filter": "basic"}`. Commands, positional
+//! arguments and flags are derived from the tool input schemas, and the call
+//! goes through the same MCP server the agents talk to, in-process.
+
+use crate::jira::Jira;
+use rmcp::ServiceExt;
+use rmcp::model::Tool;
+use serde_json::{Map, Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// Longest cell rendered in table output, in characters.
+const MAX_CELL: usize = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Table,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Print this text and exit.
+    Help(String),
+    Call {
+        tool: String,
+        arguments: Map<String, Value>,
+        format: Format,
+    },
+}
+
+/// Outcome of a tool call: its text content and whether it is an error.
+#[derive(Debug)]
+pub struct Output {
+    pub text: String,
+    pub is_error: bool,
+}
+
+/// Runs a parsed command on `jira` and prints the result. Returns `false`
+/// when the tool reported an error.
+pub async fn run(jira: Jira, command: Command) -> anyhow::Result<bool> {
+    let (tool, arguments, format) = match command {
+        Command::Help(text) => {
+            println!("{}", text);
+            return Ok(true);
+        }
+        Command::Call {
+            tool,
+            arguments,
+            format,
+        } => (tool, arguments, format),
+    };
+
+    let output = call(jira, &tool, arguments).await?;
+    if output.is_error {
+        eprintln!("{}", output.text);
+        return Ok(false);
+    }
+    match serde_json::from_str::<Value>(&output.text) {
+        Ok(value) => println!("{}", render(&value, format)),
+        Err(_) => println!("{}", output.text),
+    }
+    Ok(true)
+}
+
+/// Maps command line words to a tool and its arguments.
+///
+/// The leading words name the tool (`issue get` -> `issue_get`; a group with
+/// a single tool can be named by its first word, e.g. `search`). Remaining
+/// words fill the required parameters in order, and `--flag value` sets any
+/// parameter by name in kebab, snake or camel case.
+pub fn parse(tools: &[Tool], args: &[String]) -> anyhow::Result<Command> {
+    let words: Vec<&str> = args
+        .iter()
+        .take_while(|a| !a.starts_with('-'))
+        .map(String::as_str)
+        .collect();
+    if words.is_empty() || words[0] == "help" || words[0] == "tools" {
+        return Ok(Command::Help(overview(tools)));
+    }
+
+    let (tool, consumed) = find_tool(tools, &words).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown command '{}'. Run 'jira-rs help' for the list of commands.",
+            words.join(" ")
+        )
+    })?;
+    let schema = &tool.input_schema;
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut format = Format::Json;
+    let mut arguments = Map::new();
+    let mut positional = Vec::new();
+    let mut rest = args[consumed..].iter().peekable();
+    while let Some(arg) = rest.next() {
+        let Some(flag) = arg.strip_prefix("--").or_else(|| arg.strip_prefix('-')) else {
+            positional.push(arg.clone());
+            continue;
+        };
+        let (flag, inline) = match flag.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (flag, None),
+        };
+
+        if matches!(flag, "help" | "h") {
+            return Ok(Command::Help(usage(tool)));
+        }
+        if matches!(flag, "output" | "o") {
+            let value = inline
+                .or_else(|| rest.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("--output requires 'json' or 'table'"))?;
+            format = match value.as_str() {
+                "json" => Format::Json,
+                "table" => Format::Table,
+                other => anyhow::bail!("Unknown output format '{}': expected json or table", other),
+            };
+            continue;
+        }
+
+        let (name, property) = properties
+            .iter()
+            .find(|(name, _)| normalize(name) == normalize(flag))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown option --{} for '{}'. Run 'jira-rs {} --help' for its options.",
+                    flag,
+                    command_name(&tool.name),
+                    command_name(&tool.name)
+                )
+            })?;
+        let kind = kind(property, schema);
+        let raw = match inline {
+            Some(value) => value,
+            // A bare boolean flag means true
+            None if kind == "boolean"
+                && rest.peek().is_none_or(|next| parse_bool(next).is_none()) =>
+            {
+                "true".to_string()
+            }
+            None => rest
+                .next()
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("--{} requires a value", kebab(name)))?,
+        };
+        let value = convert(name, &raw, property, schema)?;
+        match (arguments.get_mut(name), value) {
+            // Repeated array flags accumulate
+            (Some(Value::Array(existing)), Value::Array(more)) => existing.extend(more),
+            (_, value) => {
+                arguments.insert(name.clone(), value);
+            }
+        }
+    }
+
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|name| !arguments.contains_key(*name))
+        .collect();
+    if positional.len() > required.len() {
+        anyhow::bail!(
+            "Too many arguments for '{}': {}. Run 'jira-rs {} --help' for its usage.",
+            command_name(&tool.name),
+            positional[required.len()..].join(" "),
+            command_name(&tool.name)
+        );
+    }
+    let missing: Vec<String> = required[positional.len()..]
+        .iter()
+        .map(|name| format!("<{}>", kebab(name)))
+        .collect();
+    for (name, raw) in required.iter().zip(positional) {
+        let property = properties.get(*name).cloned().unwrap_or_default();
+        arguments.insert(name.to_string(), convert(name, &raw, &property, schema)?);
+    }
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Missing {} for '{}'. Run 'jira-rs {} --help' for its usage.",
+            missing.join(" "),
+            command_name(&tool.name),
+            command_name(&tool.name)
+        );
+    }
+
+    Ok(Command::Call {
+        tool: tool.name.to_string(),
+        arguments,
+        format,
+    })
+}
+
+/// Runs one tool on `jira` through an in-memory MCP session.
+pub async fn call(jira: Jira, tool: &str, arguments: Map<String, Value>) -> anyhow::Result<Output> {
+    let (client_io, server_io) = tokio::io::duplex(1 << 20);
+    let server = tokio::spawn(async move {
+        if let Ok(service) = jira.serve(server_io).await {
+            let _ = service.waiting().await;
+        }
+    });
+
+    let (reader, mut writer) = tokio::io::split(client_io);
+    let mut lines = BufReader::new(reader).lines();
+    let mut send = async |message: Value| -> anyhow::Result<()> {
+        let mut line = message.to_string();
+        line.push('\n');
+        writer.write_all(line.as_bytes()).await?;
+        writer.flush().await?;
+        Ok(())
+    };
+
+    send(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "jira-rs-cli", "version": env!("CARGO_PKG_VERSION") }
+        }
+    }))
+    .await?;
+    send(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" })).await?;
+    send(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": { "name": tool, "arguments": arguments }
+    }))
+    .await?;
+
+    let response = loop {
+        let line = lines
+            .next_line()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("The server closed the session"))?;
+        let message: Value = serde_json::from_str(&line)?;
+        if message.get("id") == Some(&json!(2)) {
+            break message;
+        }
+    };
+    server.abort();
+
+    if let Some(error) = response.get("error") {
+        anyhow::bail!(
+            "{}",
+            error["message"].as_str().unwrap_or("tools/call failed")
+        );
+    }
+    let result = &response["result"];
+    let text = result["content"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.get("text").and_then(Value::as_str))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(Output {
+        text,
+        is_error: result["isError"].as_bool().unwrap_or(false),
+    })
+}
+
+/// Pretty JSON, or an aligned text table for humans.
+pub fn render(value: &Value, format: Format) -> String {
+    match format {
+        Format::Json => serde_json::to_string_pretty(value).unwrap_or_default(),
+        Format::Table => table(value),
+    }
+}
+
+fn find_tool<'a>(tools: &'a [Tool], words: &[&str]) -> Option<(&'a Tool, usize)> {
+    for n in (1..=words.len()).rev() {
+        let name = words[..n].join("_");
+        if let Some(tool) = tools.iter().find(|t| t.name == name) {
+            return Some((tool, n));
+        }
+    }
+    // `search` -> `search_issues` when the group has a single tool
+    let prefix = format!("{}_", words[0]);
+    let mut group = tools.iter().filter(|t| t.name.starts_with(&prefix));
+    match (group.next(), group.next()) {
+        (Some(tool), None) => Some((tool, 1)),
+        _ => None,
+    }
+}
+
+/// JSON type of a property, following `$ref` and skipping `null` variants.
+fn kind<'a>(property: &'a Value, root: &'a Map<String, Value>) -> &'a str {
+    if let Some(reference) = property.get("$ref").and_then(Value::as_str) {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        let target = root
+            .get("$defs")
+            .or_else(|| root.get("definitions"))
+            .and_then(|defs| defs.get(name));
+        return target.map(|t| kind(t, root)).unwrap_or("any");
+    }
+    match property.get("type") {
+        Some(Value::String(t)) => return t,
+        Some(Value::Array(types)) => {
+            if let Some(t) = types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|t| *t != "null")
+            {
+                return t;
+            }
+        }
+        _ => {}
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = property.get(key).and_then(Value::as_array) {
+            for variant in variants {
+                match kind(variant, root) {
+                    "null" => continue,
+                    found => return found,
+                }
+            }
+        }
+    }
+    if property.get("enum").is_some() {
+        return "string";
+    }
+    "any"
+}
+
+fn convert(
+    name: &str,
+    raw: &str,
+    property: &Value,
+    root: &Map<String, Value>,
+) -> anyhow::Result<Value> {
+    let invalid =
+        |expected: &str| anyhow::anyhow!("--{} expects {}, got '{}'", kebab(name), expected, raw);
+    Ok(match kind(property, root) {
+        "integer" => json!(raw.parse::<i64>().map_err(|_| invalid("an integer"))?),
+        "number" => json!(raw.parse::<f64>().map_err(|_| invalid("a number"))?),
+        "boolean" => json!(parse_bool(raw).ok_or_else(|| invalid("true or false"))?),
+        "array" if raw.trim_start().starts_with('[') => {
+            serde_json::from_str(raw).map_err(|_| invalid("a JSON array"))?
+        }
+        "array" => {
+            let items = property.get("items").cloned().unwrap_or_default();
+            let values = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| convert(name, v, &items, root))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Value::Array(values)
+        }
+        "string" => json!(raw),
+        // Objects and free-form values: JSON when it parses, text otherwise
+        _ => serde_json::from_str(raw).unwrap_or_else(|_| json!(raw)),
+    })
+}
+
+fn parse_bool(raw: &str) -> Option<bool> {
+    match raw.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// `issueKey` -> `issue-key`.
+fn kebab(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('-');
+            out.push(c.to_ascii_lowercase());
+        } else if c == '_' {
+            out.push('-');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn command_name(tool: &str) -> String {
+    tool.replace('_', " ")
+}
+
+fn first_sentence(text: &str) -> &str {
+    match text.find(". ") {
+        Some(idx) => &text[..=idx],
+        None => text,
+    }
+}
+
+fn overview(tools: &[Tool]) -> String {
+    let mut tools: Vec<&Tool> = tools.iter().collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    let width = tools
+        .iter()
+        .map(|t| command_name(&t.name).len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::from(
+        "Usage: jira-rs <command> [arguments] [--option value ...] [--output json|table]\n\nCommands:\n",
+    );
+    for tool in tools {
+        let description = tool.description.as_deref().unwrap_or_default();
+        out.push_str(&format!(
+            "  {:width$}  {}\n",
+            command_name(&tool.name),
+            first_sentence(description),
+            width = width
+        ));
+    }
+    out.push_str("\nRun 'jira-rs <command> --help' for the options of a command.");
+    out
+}
+
+fn usage(tool: &Tool) -> String {
+    let schema = &tool.input_schema;
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut out = format!(
+        "{}\n\nUsage: jira-rs {}",
+        tool.description.as_deref().unwrap_or_default(),
+        command_name(&tool.name)
+    );
+    for name in &required {
+        out.push_str(&format!(" <{}>", kebab(name)));
+    }
+    out.push_str(" [options]\n\nOptions:\n");
+
+    let mut names: Vec<&String> = properties.keys().collect();
+    names.sort_by_key(|name| (!required.contains(&name.as_str()), name.to_string()));
+    for name in names {
+        let property = &properties[name];
+        let mut line = format!("  --{} <{}>", kebab(name), kind(property, schema));
+        if required.contains(&name.as_str()) {
+            line.push_str(" (required)");
+        }
+        out.push_str(&line);
+        out.push('\n');
+        if let Some(description) = property.get("description").and_then(Value::as_str) {
+            for text in description.lines().filter(|l| !l.trim().is_empty()) {
+                out.push_str(&format!("      {}\n", text.trim()));
+            }
+        }
+    }
+    out.push_str("  --output <json|table>\n      Output format. Default: json");
+    out
+}
+
+fn table(value: &Value) -> String {
+    let rows = match value {
+        Value::Array(items) => items.clone(),
+        Value::Object(map) => {
+            // A single list of records, e.g. search results, is the table
+            let lists: Vec<&Vec<Value>> = map
+                .values()
+                .filter_map(Value::as_array)
+                .filter(|items| items.iter().all(Value::is_object) && !items.is_empty())
+                .collect();
+            match lists.as_slice() {
+                [items] => (*items).clone(),
+                _ => {
+                    return render_rows(
+                        &["field".to_string(), "value".to_string()],
+                        map.iter().map(|(k, v)| vec![k.clone(), cell(v)]).collect(),
+                    );
+                }
+            }
+        }
+        scalar => return cell(scalar),
+    };
+
+    if !rows.iter().all(Value::is_object) {
+        return render_rows(
+            &["value".to_string()],
+            rows.iter().map(|v| vec![cell(v)]).collect(),
+        );
+    }
+
+    // Issues keep their interesting values under "fields"
+    let records: Vec<Map<String, Value>> = rows
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|row| {
+            let mut record = Map::new();
+            for (key, value) in row {
+                match (key.as_str(), value) {
+                    ("self", _) => {}
+                    ("fields", Value::Object(fields)) => {
+                        record.extend(fields.iter().map(|(k, v)| (k.clone(), v.clone())))
+                    }
+                    _ => {
+                        record.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            record
+        })
+        .collect();
+
+    let mut columns: Vec<String> = Vec::new();
+    for record in &records {
+        for key in record.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    let cells = records
+        .iter()
+        .map(|record| {
+            columns
+                .iter()
+                .map(|c| record.get(c).map(cell).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    render_rows(&columns, cells)
+}
+
+/// Short text for a table cell: names of nested objects, lists joined.
+fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(", "),
+        Value::Object(map) => ["displayName", "name", "key", "value", "id"]
+            .iter()
+            .find_map(|k| map.get(*k).filter(|v| !v.is_object()).map(cell))
+            .unwrap_or_else(|| value.to_string()),
+        other => other.to_string(),
+    };
+    let text = text.replace(['\n', '\r', '\t'], " ");
+    if text.chars().count() > MAX_CELL {
+        let mut short: String = text.chars().take(MAX_CELL - 1).collect();
+        short.push('…');
+        short
+    } else {
+        text
+    }
+}
+
+fn render_rows(columns: &[String], rows: Vec<Vec<String>>) -> String {
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain(std::iter::once(c.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{}{}", c, " ".repeat(w - c.chars().count())))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = vec![
+        line(columns),
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("  "),
+    ];
+    out.extend(rows.iter().map(|r| line(r)));
+    out.join("\n")
+}
@@ -0,0 +1,388 @@
+use crate::auth::Auth;
+use crate::cache::MetadataCache;
+use crate::error::JiraError;
+use crate::oauth::{OAuthConfig, OAuthSession};
+use crate::retry::RetryPolicy;
+use crate::site::{Deployment, Site};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Name of the profile built from the unprefixed `JIRA_*` variables.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// `$XDG_CONFIG_HOME/jira-rs` (or `~/.config/jira-rs`), where OAuth tokens
+/// and the audit log are kept.
+pub fn config_dir() -> PathBuf {
+    std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|h| PathBuf::from(h).join(".config")))
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("jira-rs")
+}
+
+/// Settings read from `config.toml`. Top-level keys configure the `default`
+/// profile, `[profiles.<name>]` tables add named ones:
+///
+/// ```toml
+/// workspace = "mycompany"
+/// username = "me@example.com"
+/// token_command = "op read op://work/jira/token"
+///
+/// [profiles.client]
+/// base_url = "https://jira.client.com"
+/// token = "..."
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigFile {
+    /// Profile used when a tool call names none.
+    pub default_profile: Option<String>,
+    #[serde(flatten)]
+    pub default: ProfileSettings,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileSettings>,
+}
+
+/// Connection settings of one profile in the config file; environment
+/// variables take precedence over each of them.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ProfileSettings {
+    pub base_url: Option<String>,
+    pub workspace: Option<String>,
+    pub username: Option<String>,
+    pub token: Option<String>,
+    /// Shell command printing the token, e.g. a password manager CLI.
+    pub token_command: Option<String>,
+    /// `cloud`, `datacenter` or `auto`.
+    pub deployment: Option<String>,
+}
+
+impl ConfigFile {
+    /// Default location: `config.toml` in [`config_dir`].
+    pub fn default_path() -> PathBuf {
+        config_dir().join("config.toml")
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Reads `path`, or the default location when `None`. A missing file is
+    /// only an error when the path was given explicitly.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Option<Self>> {
+        let (path, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => (Self::default_path(), false),
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !explicit => return Ok(None),
+            Err(e) => anyhow::bail!("Could not read config file {}: {}", path.display(), e),
+        };
+        let file = Self::parse(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid config file {}: {}", path.display(), e))?;
+        tracing::info!("Loaded config file {}", path.display());
+        Ok(Some(file))
+    }
+}
+
+/// Connection settings of one named workspace.
+#[derive(Clone, Debug)]
+pub struct Profile {
+    pub name: String,
+    /// Any site root; takes precedence over `workspace`.
+    pub base_url: Option<String>,
+    /// Cloud subdomain (`<workspace>.atlassian.net`).
+    pub workspace: Option<String>,
+    pub username: Option<String>,
+    pub token: Option<String>,
+    /// Command whose output is the token, run when `token` is not set.
+    pub token_command: Option<String>,
+    pub deployment: Option<Deployment>,
+    pub oauth: Option<OAuthConfig>,
+}
+
+impl Profile {
+    /// Reads `{prefix}WORKSPACE`, `{prefix}BASE_URL`, `{prefix}USERNAME`,
+    /// `{prefix}TOKEN`, `{prefix}TOKEN_COMMAND`, `{prefix}DEPLOYMENT` and the
+    /// OAuth variables.
+    fn from_env(name: &str, prefix: &str) -> Self {
+        let var = |key: &str| {
+            std::env::var(format!("{}{}", prefix, key))
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        Self {
+            name: name.to_string(),
+            base_url: var("BASE_URL"),
+            workspace: var("WORKSPACE"),
+            username: var("USERNAME"),
+            token: var("TOKEN"),
+            token_command: var("TOKEN_COMMAND"),
+            deployment: var("DEPLOYMENT").and_then(|d| Deployment::parse(&d)),
+            oauth: OAuthConfig::from_env(prefix, name),
+        }
+    }
+
+    /// Fills the settings the environment left unset from the config file.
+    fn or_settings(mut self, settings: &ProfileSettings) -> Self {
+        let pick = |value: Option<String>, fallback: &Option<String>| {
+            value.or_else(|| {
+                fallback
+                    .as_ref()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+            })
+        };
+        self.base_url = pick(self.base_url, &settings.base_url);
+        self.workspace = pick(self.workspace, &settings.workspace);
+        self.username = pick(self.username, &settings.username);
+        self.token = pick(self.token, &settings.token);
+        self.token_command = pick(self.token_command, &settings.token_command);
+        self.deployment = self
+            .deployment
+            .or_else(|| settings.deployment.as_deref().and_then(Deployment::parse));
+        self
+    }
+
+    fn is_configured(&self) -> bool {
+        self.base_url.is_some() || self.workspace.is_some() || self.oauth.is_some()
+    }
+
+    /// Builds the site, detecting the deployment through `serverInfo` when it
+    /// is neither configured nor implied (Cloud workspace or OAuth).
+    pub async fn connect(self, retry: &RetryPolicy) -> anyhow::Result<Site> {
+        let (base_url, implied, auth) = if let Some(config) = self.oauth {
+            // OAuth tokens only work through the api.atlassian.com gateway (Cloud)
+            let session = OAuthSession::load(config)?;
+            let base_url = session.base_url().await;
+            (
+                base_url,
+                Some(Deployment::Cloud),
+                Auth::OAuth2(Arc::new(session)),
+            )
+        } else {
+            // base_url targets any host (Data Center, proxies, local mocks);
+            // workspace stays as the Cloud shorthand.
+            let (base_url, implied) = match (self.base_url, self.workspace) {
+                (Some(url), _) => {
+                    if !url.starts_with("http://") && !url.starts_with("https://") {
+                        anyhow::bail!(
+                            "[{}] base URL must start with http:// or https://: {}",
+                            self.name,
+                            url
+                        );
+                    }
+                    (url, None)
+                }
+                (None, Some(workspace)) => (Site::cloud_url(&workspace), Some(Deployment::Cloud)),
+                (None, None) => anyhow::bail!(
+                    "[{}] neither a base URL nor a workspace is configured",
+                    self.name
+                ),
+            };
+            let token = match (self.token, self.token_command) {
+                (Some(token), _) => token,
+                (None, Some(command)) => run_token_command(&self.name, &command).await?,
+                (None, None) => anyhow::bail!(
+                    "[{}] no API token configured: set a token or token command (JIRA_TOKEN or JIRA_TOKEN_COMMAND for the default profile)",
+                    self.name
+                ),
+            };
+            // Without a username the token is sent as a Personal Access Token (Data Center)
+            let auth = match self.username {
+                Some(username) => Auth::Basic {
+                    username,
+                    password: token,
+                },
+                None => Auth::Bearer(token),
+            };
+            (base_url, implied, auth)
+        };
+
+        let site = Site::new(&base_url, auth)
+            .with_retry_policy(retry.clone())
+            .with_cache_ttl(MetadataCache::ttl_from_env());
+
+        // An explicit deployment skips detection; "auto" or unset asks serverInfo
+        let deployment = match self.deployment.or(implied) {
+            Some(deployment) => deployment,
+            None => site.detect_deployment().await.unwrap_or_else(|e| {
+                tracing::warn!(
+                    "[{}] Could not detect Jira deployment, assuming Cloud: {}",
+                    self.name,
+                    e
+                );
+                Deployment::Cloud
+            }),
+        };
+        tracing::info!(
+            "[{}] Using Jira {:?} at {}",
+            self.name,
+            deployment,
+            base_url
+        );
+
+        Ok(site.with_deployment(deployment))
+    }
+}
+
+/// Runs `command` through the shell and returns its trimmed output as the token.
+async fn run_token_command(profile: &str, command: &str) -> anyhow::Result<String> {
+    let mut shell = if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let output = shell
+        .arg(command)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("[{}] could not run token command: {}", profile, e))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "[{}] token command failed ({}): {}",
+            profile,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        anyhow::bail!("[{}] token command printed nothing", profile);
+    }
+    Ok(token)
+}
+
+/// Workspace profiles configured in the environment and the optional config
+/// file, plus the name of the primary one.
+///
+/// The unprefixed `JIRA_*` variables form the `default` profile. Extra
+/// profiles are listed in `JIRA_PROFILES` (comma separated) and read from
+/// `JIRA_<NAME>_*`. `JIRA_DEFAULT_PROFILE` picks the primary profile.
+/// Settings missing from the environment are taken from `file`.
+pub fn load_profiles(file: Option<&ConfigFile>) -> anyhow::Result<(Vec<Profile>, String)> {
+    let empty = ConfigFile::default();
+    let file = file.unwrap_or(&empty);
+    let mut profiles = Vec::new();
+
+    let primary = Profile::from_env(DEFAULT_PROFILE, "JIRA_").or_settings(&file.default);
+    if primary.is_configured() {
+        profiles.push(primary);
+    }
+
+    let listed = std::env::var("JIRA_PROFILES").unwrap_or_default();
+    let mut names: Vec<String> = listed
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .collect();
+    for name in file.profiles.keys() {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    for name in &names {
+        let prefix = format!("JIRA_{}_", name.to_uppercase().replace('-', "_"));
+        let profile = Profile::from_env(name, &prefix).or_settings(
+            file.profiles
+                .get(name)
+                .unwrap_or(&ProfileSettings::default()),
+        );
+        if !profile.is_configured() {
+            anyhow::bail!(
+                "Profile '{}' has neither {}BASE_URL nor {}WORKSPACE (or base_url/workspace in the config file)",
+                name,
+                prefix,
+                prefix
+            );
+        }
+        profiles.push(profile);
+    }
+
+    if profiles.is_empty() {
+        anyhow::bail!(
+            "No Jira workspace configured: set JIRA_WORKSPACE or JIRA_BASE_URL, or add workspace/base_url to {}",
+            ConfigFile::default_path().display()
+        );
+    }
+
+    let default = std::env::var("JIRA_DEFAULT_PROFILE")
+        .ok()
+        .filter(|d| !d.trim().is_empty())
+        .or_else(|| file.default_profile.clone())
+        .unwrap_or_else(|| profiles[0].name.clone());
+    if !profiles.iter().any(|p| p.name == default) {
+        anyhow::bail!(
+            "JIRA_DEFAULT_PROFILE '{}' is not a configured profile",
+            default
+        );
+    }
+
+    Ok((profiles, default))
+}
+
+/// Calls `/myself` on every site and logs whether the credentials work.
+/// Problems are reported, not fatal: the server still starts so clients can
+/// see the tools and the error they get back.
+pub async fn check_credentials(sites: &BTreeMap<String, Site>) {
+    for (name, site) in sites {
+        match site.myself().await {
+            Ok(user) => tracing::info!(
+                "[{}] Authenticated as {} at {}",
+                name,
+                user.display_name
+                    .as_deref()
+                    .or(user.name.as_deref())
+                    .unwrap_or(&user.account_id),
+                site.base_url()
+            ),
+            Err(e) => tracing::error!("[{}] {}", name, credentials_diagnostic(site, &e)),
+        }
+    }
+}
+
+/// Explains a failed `/myself` probe in terms of the configuration.
+pub fn credentials_diagnostic(site: &Site, error: &JiraError) -> String {
+    let advice = match error {
+        JiraError::Http { status: 401, .. } => {
+            "The credentials were rejected. Check the username and API token (or run 'jira-rs login' again for OAuth)."
+        }
+        JiraError::Http { status: 403, .. } => {
+            "The account is authenticated but not allowed to use the REST API on this site."
+        }
+        JiraError::Http { status: 404, .. } => {
+            "The URL does not look like a Jira site. Check the workspace or base URL."
+        }
+        JiraError::Transport(_) => "The site could not be reached. Check the base URL and network.",
+        _ => "Jira tools will fail until this is fixed.",
+    };
+    format!(
+        "Credential check against {} failed: {}. {}",
+        site.base_url(),
+        error,
+        advice
+    )
+}
+
+/// Connects every profile.
+pub async fn connect_all(
+    profiles: Vec<Profile>,
+    retry: &RetryPolicy,
+) -> anyhow::Result<BTreeMap<String, Site>> {
+    let mut sites = BTreeMap::new();
+    for profile in profiles {
+        let name = profile.name.clone();
+        sites.insert(name, profile.connect(retry).await?);
+    }
+    Ok(sites)
+}
@@ -0,0 +1,77 @@
+use crate::error::JiraError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Single-use tokens that tie the execution of a destructive tool call to a
+/// previous dry run of exactly the same operation.
+#[derive(Debug)]
+pub struct Confirmations {
+    /// Destructive tools refuse to run without a token.
+    required: bool,
+    ttl: Duration,
+    pending: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl Confirmations {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+    pub fn new(required: bool) -> Self {
+        Self {
+            required,
+            ttl: Self::DEFAULT_TTL,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads `JIRA_CONFIRM_DESTRUCTIVE` (default false).
+    pub fn required_from_env() -> bool {
+        std::env::var("JIRA_CONFIRM_DESTRUCTIVE")
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false)
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Issues a token for `operation`, a string identifying the tool, target
+    /// site and arguments.
+    pub fn issue(&self, operation: &str) -> String {
+        let token: String = {
+            use rand::Rng;
+            rand::rng()
+                .sample_iter(rand::distr::Alphanumeric)
+                .take(24)
+                .map(char::from)
+                .collect()
+        };
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|_, (_, issued)| issued.elapsed() < self.ttl);
+        pending.insert(token.clone(), (operation.to_string(), Instant::now()));
+        token
+    }
+
+    /// Consumes `token`, which must have been issued for the same operation
+    /// and not be expired.
+    pub fn redeem(&self, token: &str, operation: &str) -> Result<(), JiraError> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        match pending.remove(token) {
+            Some((issued_for, issued))
+                if issued_for == operation && issued.elapsed() < self.ttl =>
+            {
+                Ok(())
+            }
+            Some((issued_for, _)) if issued_for != operation => Err(JiraError::validation(
+                "The confirmation token was issued for a different operation. Run the tool with dryRun=true again.",
+            )),
+            _ => Err(JiraError::validation(
+                "Unknown or expired confirmation token. Run the tool with dryRun=true to get a new one.",
+            )),
+        }
+    }
+}
@@ -14,6 +14,10 @@ pub struct AgileRankIssuesArgs {
     /// Rank before this issue
     #[serde(skip_serializing_if = "Option::is_none")]
     pub before_issue_key: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -26,4 +30,80 @@ pub struct BoardGetBacklogArgs {
     /// Project key to filter by
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project_key: Option<String>,
-}
\ No newline at end of file
+
+    /// Index of the first issue (default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<i64>,
+
+    /// Issues per page (default 50). With `allPages`, caps the total
+    /// number of issues instead (default and maximum 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+
+    /// Fetch every page instead of one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_pages: Option<bool>,
+
+    /// **[OPTIONAL]** Field filter (same syntax as issue_get).
+    /// Presets: "minimal", "basic", "standard", "detailed"
+    /// Custom: "id key summary status"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// A page of an Agile API listing, paged with `startAt`/`maxResults`.
+pub trait AgilePage: serde::de::DeserializeOwned {
+    /// Items on this page.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether no items follow this page.
+    fn is_last(&self) -> bool;
+
+    /// Adds the items of the page that follows this one.
+    fn append(&mut self, next: Self);
+}
+
+impl AgilePage for super::jql::IssuePage {
+    fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    fn is_last(&self) -> bool {
+        // The backlog reports a total rather than isLast
+        self.is_last.unwrap_or_else(|| match self.total {
+            Some(total) => self.start_at.unwrap_or(0) + self.issues.len() as i64 >= total,
+            None => true,
+        })
+    }
+
+    fn append(&mut self, next: Self) {
+        self.issues.extend(next.issues);
+        self.is_last = next.is_last;
+        self.total = next.total.or(self.total);
+        self.max_results = None;
+    }
+}
+
+impl AgilePage for super::sprint::SprintPage {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last.unwrap_or(true)
+    }
+
+    fn append(&mut self, next: Self) {
+        self.values.extend(next.values);
+        self.is_last = next.is_last;
+        self.max_results = None;
+    }
+}
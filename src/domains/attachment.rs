@@ -0,0 +1,239 @@
+use super::user::User;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueAttachFileArgs {
+    /// Issue ID or key (e.g., "PROJ-123").
+    pub issue_key: String,
+
+    /// Path of the file to attach, relative to the attachments directory (JIRA_ATTACHMENTS_DIR). Either path or contentBase64 is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// File content encoded as base64, for files that are not on disk.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_base64: Option<String>,
+
+    /// Name of the attachment in Jira. Required with contentBase64; defaults to the file name of path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueListAttachmentsArgs {
+    /// Issue ID or key (e.g., "PROJ-123").
+    pub issue_key: String,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentDownloadArgs {
+    /// The attachment ID, as listed by issue_list_attachments.
+    pub attachment_id: String,
+
+    /// File to write, relative to the attachments directory (JIRA_ATTACHMENTS_DIR). When it is an existing directory, the file is saved in it under the attachment's name.
+    pub path: String,
+
+    /// Replace the file if it already exists. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<bool>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentReadArgs {
+    /// The attachment ID, as listed by issue_list_attachments.
+    pub attachment_id: String,
+
+    /// Most bytes of a text attachment to return; the rest is cut off with a marker. Defaults to 65536, at most 1048576.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentDeleteArgs {
+    /// The attachment ID, as listed by issue_list_attachments.
+    pub attachment_id: String,
+
+    /// Only report what would be affected, without changing anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Token returned by a previous dry run, required to proceed when confirmation is enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_token: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// A file attached to an issue.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: String,
+    pub filename: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// Size in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// URL of the file content on the site host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// URL of a thumbnail, for images.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+}
+
+/// MIME types outside `text/*` that hold readable text.
+const TEXT_MIME_TYPES: [&str; 9] = [
+    "application/json",
+    "application/xml",
+    "application/x-ndjson",
+    "application/javascript",
+    "application/x-yaml",
+    "application/yaml",
+    "application/csv",
+    "application/x-sh",
+    "application/sql",
+];
+
+/// Extensions of text files Jira often stores as `application/octet-stream`.
+const TEXT_EXTENSIONS: [&str; 16] = [
+    "log",
+    "txt",
+    "json",
+    "csv",
+    "tsv",
+    "xml",
+    "yaml",
+    "yml",
+    "md",
+    "ini",
+    "conf",
+    "cfg",
+    "toml",
+    "properties",
+    "out",
+    "trace",
+];
+
+/// Image types MCP clients display.
+const IMAGE_MIME_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+impl Attachment {
+    /// The MIME type Jira reported, or one guessed from the file name.
+    pub fn mime(&self) -> String {
+        self.mime_type
+            .clone()
+            .map(|mime| {
+                mime.split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_lowercase()
+            })
+            .filter(|mime| !mime.is_empty() && mime != "application/octet-stream")
+            .unwrap_or_else(|| {
+                mime_guess::from_path(&self.filename)
+                    .first_or_octet_stream()
+                    .to_string()
+            })
+    }
+
+    pub fn is_image(&self) -> bool {
+        IMAGE_MIME_TYPES.contains(&self.mime().as_str())
+    }
+
+    pub fn is_text(&self) -> bool {
+        let mime = self.mime();
+        let extension = std::path::Path::new(&self.filename)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        mime.starts_with("text/")
+            || mime.ends_with("+json")
+            || mime.ends_with("+xml")
+            || TEXT_MIME_TYPES.contains(&mime.as_str())
+            || TEXT_EXTENSIONS.contains(&extension.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentList {
+    pub attachments: Vec<Attachment>,
+}
+
+/// A downloaded attachment.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentFile {
+    /// Where the file was written.
+    pub path: String,
+    pub filename: String,
+    /// Size in bytes.
+    pub size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentKind {
+    Image,
+    Text,
+}
+
+/// An attachment read by `attachment_read`. Images are sent as image
+/// content next to this description.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentContent {
+    pub id: String,
+    pub filename: String,
+    pub kind: AttachmentKind,
+    /// Type of the returned content.
+    pub mime_type: String,
+    /// Size of the attachment in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// The content of a text attachment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Whether the text was cut off at maxBytes.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Whether the image is Jira's thumbnail, because the original is too large.
+    #[serde(default)]
+    pub thumbnail: bool,
+    /// Why no content was returned, when the image could not be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
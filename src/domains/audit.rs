@@ -0,0 +1,32 @@
+use crate::audit::AuditEntry;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogQueryArgs {
+    /// Only entries written by this tool, e.g. 'issue_delete'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+
+    /// Only entries that touched this issue key (e.g. 'PROJ-123').
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_key: Option<String>,
+
+    /// Only entries at or after this RFC 3339 timestamp (e.g. '2024-05-01T00:00:00Z').
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+
+    /// Maximum number of entries to return, newest first. Default: 20, max: 200.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogPage {
+    /// File the entries were read from.
+    pub path: String,
+    /// Newest first.
+    pub entries: Vec<AuditEntry>,
+}
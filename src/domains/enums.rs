@@ -125,6 +125,7 @@ pub enum FieldPreset {
 
 impl FieldPreset {
     /// Convierte el preset a lista de campos para Jira API
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_field_list(&self) -> String {
         match self {
             Self::Minimal => "id,key".to_string(),
@@ -148,4 +149,14 @@ impl fmt::Display for FieldPreset {
     }
 }
 
-
+/// How rich text fields (descriptions, comments) are returned.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[schemars(inline)]
+pub enum ContentFormat {
+    /// Atlassian Document Format converted to Markdown
+    #[default]
+    Markdown,
+    /// Raw Atlassian Document Format JSON, as Jira returns it
+    Adf,
+}
@@ -1,104 +1,10 @@
 use super::JsonValue;
+use super::enums::FieldPreset;
 
 // ============================================================================
 // HELPER FUNCTIONS
 // ============================================================================
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy)]
-pub enum AdfStyle {
-    Paragraph,
-    Heading1,
-    Heading2,
-    Heading3,
-    Codeblock,
-}
-
-pub fn text_to_adf(text: &str, style: AdfStyle) -> JsonValue {
-    match style {
-        AdfStyle::Heading1 => JsonValue(serde_json::json!({
-            "version": 1,
-            "type": "doc",
-            "content": [
-                {
-                    "type": "heading",
-                    "attrs": { "level": 1 },
-                    "content": [
-                        {
-                            "type": "text",
-                            "text": text
-                        }
-                    ]
-                }
-            ]
-        })),
-        AdfStyle::Heading2 => JsonValue(serde_json::json!({
-            "version": 1,
-            "type": "doc",
-            "content": [
-                {
-                    "type": "heading",
-                    "attrs": { "level": 2 },
-                    "content": [
-                        {
-                            "type": "text",
-                            "text": text
-                        }
-                    ]
-                }
-            ]
-        })),
-        AdfStyle::Heading3 => JsonValue(serde_json::json!({
-            "version": 1,
-            "type": "doc",
-            "content": [
-                {
-                    "type": "heading",
-                    "attrs": { "level": 3 },
-                    "content": [
-                        {
-                            "type": "text",
-                            "text": text
-                        }
-                    ]
-                }
-            ]
-        })),
-        AdfStyle::Codeblock => JsonValue(serde_json::json!({
-            "version": 1,
-            "type": "doc",
-            "content": [
-                {
-                    "type": "codeBlock",
-                    "content": [
-                        {
-                            "type": "text",
-                            "text": text
-                        }
-                    ]
-                }
-            ]
-        })),
-        AdfStyle::Paragraph => JsonValue(serde_json::json!({
-            "version": 1,
-            "type": "doc",
-            "content": [
-                {
-                    "type": "paragraph",
-                    "content": [
-                        {
-                            "type": "text",
-                            "text": text
-                        }
-                    ]
-                }
-            ]
-        })),
-    }
-}
-
-use super::enums::FieldPreset;
-
 /// Parsea un string de filtro en formato Jira API.
 /// Soporta:
 /// - Presets: "basic", "standard", "minimal"
@@ -125,3 +31,827 @@ pub fn parse_field_filter(filter: &str) -> String {
     // Retornar sin cambios (ya está en formato Jira)
     trimmed.to_string()
 }
+
+// ============================================================================
+// ADF TO MARKDOWN
+// ============================================================================
+
+use serde_json::Value;
+
+/// Whether `value` is an Atlassian Document Format document.
+pub fn is_adf(value: &Value) -> bool {
+    value.get("type").and_then(Value::as_str) == Some("doc") && value.get("content").is_some()
+}
+
+/// Renders an ADF document as Markdown. Nodes without a Markdown
+/// equivalent keep their text (panels become quotes, media a placeholder).
+pub fn adf_to_markdown(doc: &Value) -> String {
+    render_blocks(children(doc), "\n\n")
+}
+
+/// Replaces every ADF document inside `value` (descriptions, comment
+/// bodies, rich text custom fields...) with its Markdown rendering.
+pub fn adf_to_markdown_in(value: &mut Value) {
+    if is_adf(value) {
+        *value = Value::String(adf_to_markdown(value));
+        return;
+    }
+    match value {
+        Value::Object(map) => map.values_mut().for_each(adf_to_markdown_in),
+        Value::Array(items) => items.iter_mut().for_each(adf_to_markdown_in),
+        _ => {}
+    }
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|attrs| attrs.get(name))
+}
+
+fn attr_str<'a>(node: &'a Value, name: &str) -> Option<&'a str> {
+    attr(node, name).and_then(Value::as_str)
+}
+
+fn render_blocks(nodes: &[Value], separator: &str) -> String {
+    nodes
+        .iter()
+        .map(render_block)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Prefixes the first line of `text` with `first` and the others with `rest`.
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_block(node: &Value) -> String {
+    let node_type = node.get("type").and_then(Value::as_str).unwrap_or_default();
+    match node_type {
+        "paragraph" => render_inline(children(node)),
+        "heading" => {
+            let level = attr(node, "level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6);
+            format!(
+                "{} {}",
+                "#".repeat(level as usize),
+                render_inline(children(node))
+            )
+        }
+        "bulletList" => children(node)
+            .iter()
+            .map(|item| prefix_lines(&render_blocks(children(item), "\n"), "- ", "  "))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "orderedList" => {
+            let start = attr(node, "order").and_then(Value::as_u64).unwrap_or(1);
+            children(node)
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = format!("{}. ", start + i as u64);
+                    let indent = " ".repeat(marker.len());
+                    prefix_lines(&render_blocks(children(item), "\n"), &marker, &indent)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        "taskList" => children(node)
+            .iter()
+            .map(|item| {
+                if item.get("type").and_then(Value::as_str) == Some("taskList") {
+                    return prefix_lines(&render_block(item), "  ", "  ");
+                }
+                let marker = if attr_str(item, "state") == Some("DONE") {
+                    "- [x] "
+                } else {
+                    "- [ ] "
+                };
+                prefix_lines(&render_inline(children(item)), marker, "      ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "decisionList" => children(node)
+            .iter()
+            .map(|item| prefix_lines(&render_inline(children(item)), "- ✓ ", "    "))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "codeBlock" => {
+            let code: String = children(node)
+                .iter()
+                .filter_map(|text| text.get("text").and_then(Value::as_str))
+                .collect();
+            format!(
+                "```{}\n{}\n```",
+                attr_str(node, "language").unwrap_or_default(),
+                code.trim_end_matches('\n')
+            )
+        }
+        "blockquote" => prefix_lines(&render_blocks(children(node), "\n\n"), "> ", "> "),
+        "panel" => {
+            let kind = attr_str(node, "panelType").unwrap_or("info");
+            let mut chars = kind.chars();
+            let label: String = chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default();
+            let body = render_blocks(children(node), "\n\n");
+            prefix_lines(&format!("**{}:** {}", label, body), "> ", "> ")
+        }
+        "rule" => "---".to_string(),
+        "table" => render_table(node),
+        "mediaSingle" | "mediaGroup" => children(node)
+            .iter()
+            .map(render_media)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "media" => render_media(node),
+        "expand" | "nestedExpand" => {
+            let body = render_blocks(children(node), "\n\n");
+            match attr_str(node, "title").filter(|t| !t.is_empty()) {
+                Some(title) => format!("**{}**\n\n{}", title, body),
+                None => body,
+            }
+        }
+        "blockCard" | "embedCard" => attr_str(node, "url")
+            .map(|url| format!("<{}>", url))
+            .unwrap_or_default(),
+        // Unknown blocks keep their content, as blocks or as inline text
+        _ => {
+            let content = children(node);
+            if content.iter().any(|child| child.get("text").is_some()) {
+                render_inline(content)
+            } else {
+                render_blocks(content, "\n\n")
+            }
+        }
+    }
+}
+
+fn render_table(table: &Value) -> String {
+    let rows: Vec<Vec<String>> = children(table)
+        .iter()
+        .map(|row| {
+            children(row)
+                .iter()
+                .map(|cell| {
+                    render_blocks(children(cell), "\n")
+                        .replace('|', "\\|")
+                        .replace('\n', "<br>")
+                })
+                .collect()
+        })
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let line = |cells: &[String]| {
+        let mut cells = cells.to_vec();
+        cells.resize(columns, String::new());
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![line(&rows[0]), line(&vec!["---".to_string(); columns])];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn render_media(node: &Value) -> String {
+    let name = attr_str(node, "alt")
+        .filter(|alt| !alt.is_empty())
+        .or_else(|| attr_str(node, "id"))
+        .unwrap_or("file");
+    match attr_str(node, "url") {
+        Some(url) => format!("![{}]({})", name, url),
+        None => format!("[media: {}]", name),
+    }
+}
+
+fn render_inline(nodes: &[Value]) -> String {
+    nodes.iter().map(render_inline_node).collect()
+}
+
+fn render_inline_node(node: &Value) -> String {
+    let node_type = node.get("type").and_then(Value::as_str).unwrap_or_default();
+    match node_type {
+        "text" => render_text(node),
+        "hardBreak" => "\n".to_string(),
+        "mention" => {
+            let text = attr_str(node, "text")
+                .or_else(|| attr_str(node, "id"))
+                .unwrap_or_default();
+            if text.starts_with('@') {
+                text.to_string()
+            } else {
+                format!("@{}", text)
+            }
+        }
+        "emoji" => attr_str(node, "text")
+            .or_else(|| attr_str(node, "shortName"))
+            .unwrap_or_default()
+            .to_string(),
+        "inlineCard" => attr_str(node, "url")
+            .map(|url| format!("<{}>", url))
+            .unwrap_or_default(),
+        "date" => attr_str(node, "timestamp")
+            .and_then(|ms| ms.parse::<i64>().ok())
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        "status" => attr_str(node, "text")
+            .map(|text| format!("[{}]", text.to_uppercase()))
+            .unwrap_or_default(),
+        "mediaInline" => render_media(node),
+        _ => node
+            .get("text")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| render_inline(children(node))),
+    }
+}
+
+fn render_text(node: &Value) -> String {
+    let mut text = node
+        .get("text")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let marks = node
+        .get("marks")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let has = |mark: &str| {
+        marks
+            .iter()
+            .any(|m| m.get("type").and_then(Value::as_str) == Some(mark))
+    };
+
+    if has("code") {
+        text = format!("`{}`", text);
+    } else if !text.trim().is_empty() {
+        // Keep surrounding spaces outside the emphasis markers
+        let start = text.len() - text.trim_start().len();
+        let end = text.trim_end().len();
+        let mut inner = text[start..end].to_string();
+        for (mark, wrapper) in [("strike", "~~"), ("em", "*"), ("strong", "**")] {
+            if has(mark) {
+                inner = format!("{}{}{}", wrapper, inner, wrapper);
+            }
+        }
+        text = format!("{}{}{}", &text[..start], inner, &text[end..]);
+    }
+
+    let href = marks
+        .iter()
+        .find(|m| m.get("type").and_then(Value::as_str) == Some("link"))
+        .and_then(|m| attr_str(m, "href"));
+    match href {
+        Some(href) => format!("[{}]({})", text, href),
+        None => text,
+    }
+}
+
+// ============================================================================
+// MARKDOWN TO ADF
+// ============================================================================
+
+/// Converts Markdown to an ADF document: headings, nested and task lists,
+/// fenced code, block quotes, tables, rules, links and inline marks.
+/// Anything else is kept as text.
+pub fn markdown_to_adf(markdown: &str) -> JsonValue {
+    let text = markdown.replace("\r\n", "\n").replace('\t', "    ");
+    let lines: Vec<&str> = text.lines().collect();
+    JsonValue(serde_json::json!({
+        "version": 1,
+        "type": "doc",
+        "content": parse_blocks(&lines),
+    }))
+}
+
+/// Leading spaces of `line`. Other whitespace, such as non-breaking
+/// spaces, is content: counting it would slice lines inside a character.
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+struct ListMarker<'a> {
+    ordered: bool,
+    /// First number of an ordered list.
+    start: u64,
+    /// Marker and the spaces after it.
+    width: usize,
+    rest: &'a str,
+}
+
+fn list_marker(line: &str) -> Option<ListMarker<'_>> {
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    let (ordered, start, marker_len) = match line.as_bytes().first()? {
+        b'-' | b'*' | b'+' => (false, 1, 1),
+        b'0'..=b'9' if digits <= 9 && matches!(line.as_bytes().get(digits), Some(b'.' | b')')) => {
+            (true, line[..digits].parse().ok()?, digits + 1)
+        }
+        _ => return None,
+    };
+    let after = &line[marker_len..];
+    if !after.is_empty() && !after.starts_with(' ') {
+        return None;
+    }
+    let spaces = indent_of(after).clamp(1, 4);
+    Some(ListMarker {
+        ordered,
+        start,
+        width: marker_len + spaces.min(after.len()).max(1),
+        rest: after.trim_start(),
+    })
+}
+
+fn fence(line: &str) -> Option<(&str, &str)> {
+    ["```", "~~~"]
+        .into_iter()
+        .find(|f| line.starts_with(f))
+        .map(|f| (f, line.trim_start_matches(f.as_bytes()[0] as char).trim()))
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.bytes().take_while(|b| *b == b'#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')))
+        .then(|| (level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|c| compact.chars().all(|x| x == *c))
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line
+        .strip_suffix('|')
+        .filter(|l| !l.ends_with('\\'))
+        .unwrap_or(line);
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.into_iter().map(|c| c.trim().to_string()).collect()
+}
+
+fn is_table_start(lines: &[&str], i: usize) -> bool {
+    let Some(separator) = lines.get(i + 1) else {
+        return false;
+    };
+    lines[i].contains('|')
+        && separator.contains('-')
+        && table_cells(separator).iter().all(|cell| {
+            let cell = cell.trim_start_matches(':').trim_end_matches(':');
+            !cell.is_empty() && cell.chars().all(|c| c == '-')
+        })
+}
+
+/// Whether `line` starts a block other than a paragraph.
+fn starts_block(lines: &[&str], i: usize) -> bool {
+    let line = lines[i].trim_start();
+    fence(line).is_some()
+        || heading(line).is_some()
+        || is_rule(line)
+        || line.starts_with('>')
+        || list_marker(line).is_some_and(|m| !m.rest.is_empty())
+        || is_table_start(lines, i)
+}
+
+fn parse_blocks(lines: &[&str]) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim_start();
+        if line.is_empty() {
+            i += 1;
+        } else if let Some((marker, language)) = fence(line) {
+            let indent = indent_of(lines[i]);
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with(marker) {
+                let strip = indent_of(lines[i]).min(indent);
+                code.push(&lines[i][strip..]);
+                i += 1;
+            }
+            i += 1;
+            let mut block = serde_json::json!({ "type": "codeBlock" });
+            if !language.is_empty() {
+                block["attrs"] = serde_json::json!({ "language": language });
+            }
+            let code = code.join("\n");
+            if !code.is_empty() {
+                block["content"] = serde_json::json!([{ "type": "text", "text": code }]);
+            }
+            blocks.push(block);
+        } else if let Some((level, text)) = heading(line) {
+            blocks.push(serde_json::json!({
+                "type": "heading",
+                "attrs": { "level": level },
+                "content": parse_inline(text),
+            }));
+            i += 1;
+        } else if is_rule(line) {
+            blocks.push(serde_json::json!({ "type": "rule" }));
+            i += 1;
+        } else if line.starts_with('>') {
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let inner = &lines[i].trim_start()[1..];
+                quoted.push(inner.strip_prefix(' ').unwrap_or(inner));
+                i += 1;
+            }
+            blocks.push(serde_json::json!({
+                "type": "blockquote",
+                "content": parse_blocks(&quoted),
+            }));
+        } else if list_marker(line).is_some() {
+            blocks.push(parse_list(lines, &mut i));
+        } else if is_table_start(lines, i) {
+            let mut rows = vec![table_row(lines[i], "tableHeader")];
+            i += 2;
+            while i < lines.len() && lines[i].contains('|') && !lines[i].trim().is_empty() {
+                rows.push(table_row(lines[i], "tableCell"));
+                i += 1;
+            }
+            blocks.push(serde_json::json!({ "type": "table", "content": rows }));
+        } else {
+            let mut paragraph = vec![line];
+            i += 1;
+            while i < lines.len() && !lines[i].trim().is_empty() && !starts_block(lines, i) {
+                paragraph.push(lines[i].trim_start());
+                i += 1;
+            }
+            blocks.push(serde_json::json!({
+                "type": "paragraph",
+                "content": parse_lines(&paragraph),
+            }));
+        }
+    }
+    blocks
+}
+
+/// Inline content of consecutive lines, separated by hard breaks.
+fn parse_lines(lines: &[&str]) -> Vec<Value> {
+    let mut content = Vec::new();
+    for (n, line) in lines.iter().enumerate() {
+        if n > 0 {
+            content.push(serde_json::json!({ "type": "hardBreak" }));
+        }
+        let line = line.strip_suffix('\\').unwrap_or(line);
+        content.extend(parse_inline(line.trim_end()));
+    }
+    content
+}
+
+fn table_row(line: &str, cell_type: &str) -> Value {
+    let cells: Vec<Value> = table_cells(line)
+        .iter()
+        .map(|cell| {
+            let parts: Vec<&str> = cell.split("<br>").collect();
+            serde_json::json!({
+                "type": cell_type,
+                "content": [{ "type": "paragraph", "content": parse_lines(&parts) }],
+            })
+        })
+        .collect();
+    serde_json::json!({ "type": "tableRow", "content": cells })
+}
+
+fn parse_list(lines: &[&str], i: &mut usize) -> Value {
+    let base = indent_of(lines[*i]);
+    let ordered = list_marker(lines[*i].trim_start()).is_some_and(|m| m.ordered);
+    let start = list_marker(lines[*i].trim_start()).map_or(1, |m| m.start);
+    let mut items: Vec<(Option<bool>, Vec<&str>)> = Vec::new();
+
+    while *i < lines.len() {
+        let line = lines[*i];
+        let marker = list_marker(line.trim_start())
+            .filter(|m| m.ordered == ordered && indent_of(line) == base);
+        let Some(marker) = marker else {
+            break;
+        };
+
+        // "[ ] " and "[x] " make a task
+        let (task, first) = match marker.rest.get(..4) {
+            Some("[ ] ") if !ordered => (Some(false), &marker.rest[4..]),
+            Some("[x] " | "[X] ") if !ordered => (Some(true), &marker.rest[4..]),
+            _ => (None, marker.rest),
+        };
+        // Switching between tasks and plain items starts a new list
+        if items
+            .first()
+            .is_some_and(|(first_task, _)| first_task.is_some() != task.is_some())
+        {
+            break;
+        }
+        let content_indent = base + marker.width;
+        let mut item = vec![first];
+        *i += 1;
+        while *i < lines.len() {
+            let next = lines[*i];
+            let indent = indent_of(next);
+            if next.trim().is_empty() {
+                // A blank line ends the list unless indented content follows
+                let more = lines[*i + 1..]
+                    .iter()
+                    .find(|l| !l.trim().is_empty())
+                    .is_some_and(|l| indent_of(l) > base);
+                if !more {
+                    break;
+                }
+                item.push("");
+            } else if indent > base {
+                item.push(&next[indent.min(content_indent)..]);
+            } else if !starts_block(lines, *i) && item.last().is_some_and(|l| !l.is_empty()) {
+                // Lazy continuation of the item's paragraph
+                item.push(next.trim_start());
+            } else {
+                break;
+            }
+            *i += 1;
+        }
+        items.push((task, item));
+
+        // Blank lines between items of the same list
+        let mut peek = *i;
+        while peek < lines.len() && lines[peek].trim().is_empty() {
+            peek += 1;
+        }
+        if peek > *i
+            && lines.get(peek).is_some_and(|l| {
+                indent_of(l) == base
+                    && list_marker(l.trim_start()).is_some_and(|m| m.ordered == ordered)
+            })
+        {
+            *i = peek;
+        }
+    }
+
+    if items.first().is_some_and(|(task, _)| task.is_some()) {
+        return task_list(items);
+    }
+
+    let content: Vec<Value> = items
+        .into_iter()
+        .map(|(_, item)| {
+            let mut blocks = parse_blocks(&item);
+            if blocks.first().is_none_or(|b| b["type"] != "paragraph") {
+                blocks.insert(0, serde_json::json!({ "type": "paragraph", "content": [] }));
+            }
+            serde_json::json!({ "type": "listItem", "content": blocks })
+        })
+        .collect();
+    let mut list = serde_json::json!({
+        "type": if ordered { "orderedList" } else { "bulletList" },
+        "content": content,
+    });
+    if ordered && start != 1 {
+        list["attrs"] = serde_json::json!({ "order": start });
+    }
+    list
+}
+
+fn task_list(items: Vec<(Option<bool>, Vec<&str>)>) -> Value {
+    let mut content = Vec::new();
+    for (n, (done, item)) in items.into_iter().enumerate() {
+        let blocks = parse_blocks(&item);
+        let mut inline = Vec::new();
+        let mut nested = Vec::new();
+        for block in blocks {
+            match block["type"].as_str() {
+                Some("taskList") => nested.push(block),
+                Some("paragraph" | "heading") => {
+                    if !inline.is_empty() {
+                        inline.push(serde_json::json!({ "type": "hardBreak" }));
+                    }
+                    inline.extend(block["content"].as_array().cloned().unwrap_or_default());
+                }
+                // Task items only hold text and nested tasks
+                _ => {}
+            }
+        }
+        content.push(serde_json::json!({
+            "type": "taskItem",
+            "attrs": {
+                "localId": format!("task-{}", n + 1),
+                "state": if done == Some(true) { "DONE" } else { "TODO" },
+            },
+            "content": inline,
+        }));
+        content.extend(nested);
+    }
+    serde_json::json!({
+        "type": "taskList",
+        "attrs": { "localId": "tasks" },
+        "content": content,
+    })
+}
+
+fn parse_inline(text: &str) -> Vec<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut nodes = Vec::new();
+    inline_nodes(&chars, &[], &mut nodes);
+    nodes
+}
+
+/// Index of the first `delimiter` in `chars[from..]` not preceded by a
+/// backslash.
+fn find_delimiter(chars: &[char], from: usize, delimiter: &str) -> Option<usize> {
+    let delimiter: Vec<char> = delimiter.chars().collect();
+    (from..chars.len())
+        .find(|&i| chars[i..].starts_with(&delimiter) && (i == 0 || chars[i - 1] != '\\'))
+}
+
+fn push_text(nodes: &mut Vec<Value>, text: &str, marks: &[Value]) {
+    if text.is_empty() {
+        return;
+    }
+    // Merge with the previous node when the marks are the same
+    if let Some(last) = nodes.last_mut()
+        && last["type"] == "text"
+        && last
+            .get("marks")
+            .map_or(marks.is_empty(), |m| m.as_array() == Some(&marks.to_vec()))
+    {
+        let merged = format!("{}{}", last["text"].as_str().unwrap_or_default(), text);
+        last["text"] = Value::String(merged);
+        return;
+    }
+    let mut node = serde_json::json!({ "type": "text", "text": text });
+    if !marks.is_empty() {
+        node["marks"] = Value::Array(marks.to_vec());
+    }
+    nodes.push(node);
+}
+
+fn with_mark(marks: &[Value], mark: Value) -> Vec<Value> {
+    let mut marks = marks.to_vec();
+    if !marks.contains(&mark) {
+        marks.push(mark);
+    }
+    marks
+}
+
+fn inline_nodes(chars: &[char], marks: &[Value], nodes: &mut Vec<Value>) {
+    let mut text = String::new();
+    let mut i = 0;
+    let flush = |text: &mut String, nodes: &mut Vec<Value>| {
+        push_text(nodes, text, marks);
+        text.clear();
+    };
+    let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric());
+
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = &chars[i..];
+
+        // Escaped punctuation is literal
+        if c == '\\' && chars.get(i + 1).is_some_and(|n| n.is_ascii_punctuation()) {
+            text.push(chars[i + 1]);
+            i += 2;
+            continue;
+        }
+
+        if c == '`' {
+            let ticks = rest.iter().take_while(|c| **c == '`').count();
+            let delimiter = "`".repeat(ticks);
+            if let Some(end) = find_delimiter(chars, i + ticks, &delimiter) {
+                flush(&mut text, nodes);
+                let code: String = chars[i + ticks..end].iter().collect();
+                // Code only combines with links in ADF
+                let mut code_marks: Vec<Value> = marks
+                    .iter()
+                    .filter(|m| m["type"] == "link")
+                    .cloned()
+                    .collect();
+                code_marks.push(serde_json::json!({ "type": "code" }));
+                push_text(nodes, code.trim(), &code_marks);
+                i = end + ticks;
+                continue;
+            }
+        }
+
+        if c == '<'
+            && let Some(end) = find_delimiter(chars, i + 1, ">")
+        {
+            let url: String = chars[i + 1..end].iter().collect();
+            if (url.starts_with("http://") || url.starts_with("https://")) && !url.contains(' ') {
+                flush(&mut text, nodes);
+                nodes.push(serde_json::json!({ "type": "inlineCard", "attrs": { "url": url } }));
+                i = end + 1;
+                continue;
+            }
+        }
+
+        // [text](url) and ![alt](url)
+        let image = c == '!' && chars.get(i + 1) == Some(&'[');
+        if (c == '[' || image)
+            && let Some((label, href, end)) = link_at(chars, if image { i + 1 } else { i })
+        {
+            flush(&mut text, nodes);
+            let link = serde_json::json!({ "type": "link", "attrs": { "href": href } });
+            let label = if label.is_empty() {
+                href.chars().collect()
+            } else {
+                label
+            };
+            inline_nodes(&label, &with_mark(marks, link), nodes);
+            i = end;
+            continue;
+        }
+
+        let emphasis = [
+            ("**", "strong"),
+            ("__", "strong"),
+            ("~~", "strike"),
+            ("*", "em"),
+            ("_", "em"),
+        ];
+        let matched = emphasis.iter().find_map(|(delimiter, mark)| {
+            let width = delimiter.chars().count();
+            let opens = rest.starts_with(&delimiter.chars().collect::<Vec<_>>())
+                && chars.get(i + width).is_some_and(|n| !n.is_whitespace());
+            // Underscores inside words (snake_case) are not emphasis
+            if !opens
+                || (delimiter.starts_with('_')
+                    && is_word(i.checked_sub(1).and_then(|p| chars.get(p))))
+            {
+                return None;
+            }
+            let end = find_delimiter(chars, i + width + 1, delimiter)?;
+            let closes = !chars[end - 1].is_whitespace()
+                && (!delimiter.starts_with('_') || !is_word(chars.get(end + width)));
+            (closes && end > i + width).then_some((width, end, *mark))
+        });
+        if let Some((width, end, mark)) = matched {
+            flush(&mut text, nodes);
+            let mark = serde_json::json!({ "type": mark });
+            inline_nodes(&chars[i + width..end], &with_mark(marks, mark), nodes);
+            i = end + width;
+            continue;
+        }
+
+        text.push(c);
+        i += 1;
+    }
+    flush(&mut text, nodes);
+}
+
+/// Parses `[label](href)` at `chars[start]`, returning the label, the
+/// target and the index after the closing parenthesis.
+fn link_at(chars: &[char], start: usize) -> Option<(Vec<char>, String, usize)> {
+    let mut depth = 0;
+    let close = (start..chars.len()).find(|&i| {
+        match chars[i] {
+            '[' if i == start || chars[i - 1] != '\\' => depth += 1,
+            ']' if chars[i - 1] != '\\' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = find_delimiter(chars, close + 2, ")")?;
+    let target: String = chars[close + 2..end].iter().collect();
+    // Drop an optional "title"
+    let href = target.split_whitespace().next()?.to_string();
+    Some((chars[start + 1..close].to_vec(), href, end + 1))
+}
@@ -1,4 +1,4 @@
-use super::enums;
+use super::{JsonValue, enums, user::User};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,7 +15,7 @@ pub struct IssueCreateArgs {
     /// The issue title.
     pub summary: String,
 
-    /// Plain text description (will be auto-converted to ADF).
+    /// Description in Markdown (converted to ADF on Cloud). Mention people with `@Name`, `@email` or `[~accountId:ID]`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
@@ -42,6 +42,10 @@ pub struct IssueCreateArgs {
     /// Story point estimate (Next-Gen/Team-managed projects).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub story_point_estimate: Option<f64>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -52,6 +56,10 @@ pub struct IssueUpdateStatusArgs {
     
     /// Target status. Values: To Do, In Progress, Done, In Review, Blocked, Cancelled.
     pub status: enums::Status,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -62,6 +70,10 @@ pub struct IssueAssignArgs {
     
     /// Assignee identifier: "me", "unassigned", or Account ID.
     pub assignee: String,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -73,6 +85,7 @@ pub struct IssueEditDetailsArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
 
+    /// New description in Markdown (converted to ADF on Cloud).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
@@ -87,6 +100,10 @@ pub struct IssueEditDetailsArgs {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Vec<String>>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -97,6 +114,10 @@ pub struct IssueSetStoryPointsArgs {
 
     /// Numeric estimation.
     pub story_points: f64,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -105,8 +126,12 @@ pub struct IssueAddCommentArgs {
     /// Issue ID or key.
     pub issue_key: String,
     
-    /// Comment text.
+    /// Comment text in Markdown (converted to ADF on Cloud). Mention people with `@Name`, `@email` or `[~accountId:ID]`.
     pub comment: String,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -120,6 +145,10 @@ pub struct IssueLinkArgs {
     
     /// Link type. Values: Blocks, Is blocked by, Clones, Relates, Duplicates.
     pub link_type: enums::LinkType,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -135,9 +164,13 @@ pub struct IssueLogWorkArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub started: Option<String>,
     
-    /// Optional comment.
+    /// Optional comment in Markdown (converted to ADF on Cloud).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -149,6 +182,18 @@ pub struct IssueDeleteArgs {
     /// Whether to delete subtasks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delete_subtasks: Option<bool>,
+
+    /// Only report what would be affected, without changing anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Token returned by a previous dry run, required to proceed when confirmation is enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_token: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -156,6 +201,18 @@ pub struct IssueDeleteArgs {
 pub struct IssueArchiveArgs {
     /// List of issue keys or IDs to archive.
     pub issue_keys: Vec<String>,
+
+    /// Only report what would be affected, without changing anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Token returned by a previous dry run, required to proceed when confirmation is enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_token: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -163,6 +220,10 @@ pub struct IssueArchiveArgs {
 pub struct IssueUnarchiveArgs {
     /// List of issue keys or IDs to unarchive.
     pub issue_keys: Vec<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -173,6 +234,10 @@ pub struct IssueDeleteCommentArgs {
     
     /// Comment ID.
     pub comment_id: String,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -180,6 +245,18 @@ pub struct IssueDeleteCommentArgs {
 pub struct IssueDeleteLinkArgs {
     /// Link ID.
     pub link_id: String,
+
+    /// Only report what would be affected, without changing anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Token returned by a previous dry run, required to proceed when confirmation is enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_token: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -190,6 +267,10 @@ pub struct IssueSetParentArgs {
 
     /// Parent issue key (e.g., "PROJ-100" - the Epic). Set to empty string to remove parent.
     pub parent_key: String,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -219,6 +300,15 @@ pub struct IssueGetArgs {
     /// ```
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<String>,
+
+    /// Format of descriptions and comments: "markdown" (default) or "adf"
+    /// for the raw Atlassian Document Format JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<enums::ContentFormat>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -233,13 +323,85 @@ pub struct CreatedIssue {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Issue {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub id: String,
     pub key: String,
-    #[serde(rename = "self")]
+    #[serde(rename = "self", default, skip_serializing_if = "String::is_empty")]
     pub self_link: String,
+    /// Field values by field ID, limited to the requested fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub fields: HashMap<String, serde_json::Value>,
 }
 
+impl Issue {
+    /// Converts the ADF fields (description, comments, rich text custom
+    /// fields) to Markdown unless `format` asks for raw ADF.
+    pub fn render(&mut self, format: enums::ContentFormat) {
+        if format == enums::ContentFormat::Markdown {
+            self.fields
+                .values_mut()
+                .for_each(super::helpers::adf_to_markdown_in);
+        }
+    }
+}
+
+/// A comment as returned by Jira.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    #[serde(rename = "self", skip_serializing_if = "Option::is_none")]
+    pub self_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    /// ADF document on Cloud, wiki markup on Data Center.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    /// Any other attributes Jira returned.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A worklog entry as returned by Jira.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Worklog {
+    pub id: String,
+    #[serde(rename = "self", skip_serializing_if = "Option::is_none")]
+    pub self_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent_seconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<JsonValue>,
+    /// Any other attributes Jira returned.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Result of archiving or restoring issues.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveResult {
+    #[serde(default)]
+    pub number_of_issues_updated: u64,
+    /// Keys that were updated (Data Center, which handles issues one by one).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<Vec<String>>,
+    /// Failures by issue key or error category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<JsonValue>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct TransitionResponse {
     pub transitions: Vec<Transition>,
@@ -255,6 +417,8 @@ pub(crate) struct Transition {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct TransitionTo {
+    #[serde(default)]
+    pub id: Option<String>,
     pub name: String,
     pub status_category: Option<StatusCategory>,
 }
@@ -274,11 +438,46 @@ pub struct FieldsListArgs {
     /// - Omit for all fields
     #[serde(skip_serializing_if = "Option::is_none")]
     pub field_type: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListIssueTypesArgs {
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
+/// Summary of a field returned by `fields_list`.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct ListIssueTypesArgs {}
+pub struct FieldSummary {
+    /// Field ID to use in filters and edits, e.g. `customfield_10016`.
+    pub id: String,
+    pub name: String,
+    /// Schema type such as `string`, `number`, `array` or `user`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub custom: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldList {
+    pub total: usize,
+    pub fields: Vec<FieldSummary>,
+    pub usage: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueTypeList {
+    pub issue_types: Vec<IssueTypeDetails>,
+}
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -24,9 +24,62 @@ pub struct SearchIssuesArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
 
+    /// Index of the first result (Data Center only, default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<i64>,
+
+    /// `nextPageToken` of a previous call, to fetch the page after it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
+
+    /// Fetch every page instead of one. `limit` then caps the total number
+    /// of issues (default and maximum 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_pages: Option<bool>,
+
     /// **[OPTIONAL]** Field filter (same syntax as issue_get).
     /// Presets: "minimal", "basic", "standard", "detailed"
     /// Custom: "id key summary status"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<String>,
-}
\ No newline at end of file
+
+    /// Format of descriptions and comments: "markdown" (default) or "adf"
+    /// for the raw Atlassian Document Format JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<super::enums::ContentFormat>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// A page of issues as Jira returns it, from a search or a board backlog.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuePage {
+    #[serde(default)]
+    pub issues: Vec<super::issue::Issue>,
+    /// Token for the next page (Cloud search).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_last: Option<bool>,
+    /// Offset paging (Data Center search and board backlogs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_results: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+}
+
+/// Issues found by `search_issues`. Pass `nextPageToken` back as
+/// `pageToken` to continue; it is absent on the last page.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage {
+    pub issues: Vec<super::issue::Issue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    pub is_last: bool,
+}
@@ -1,10 +1,15 @@
 pub mod agile;
+pub mod attachment;
+pub mod audit;
 pub mod enums;
 pub mod helpers;
 pub mod issue;
 pub mod jql;
+pub mod outcome;
 pub mod sprint;
+pub mod undo;
 pub mod user;
+pub mod workspace;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,71 @@
+use super::JsonValue;
+use crate::journal::JournalEntry;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Result of a tool that changes Jira without returning the changed resource.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Outcome {
+    pub success: bool,
+    /// What was done, for humans.
+    pub message: String,
+    /// Pass to `undo_operation` to revert this change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undo_id: Option<u64>,
+    /// The reverted operation, returned by the undo tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<JournalEntry>,
+}
+
+impl Outcome {
+    pub fn success(message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            message: message.into(),
+            undo_id: None,
+            operation: None,
+        }
+    }
+}
+
+/// What a destructive tool would do, returned instead of running it on a
+/// dry run or when confirmation is enforced.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRun {
+    /// Always `true`: nothing was changed.
+    pub dry_run: bool,
+    pub message: String,
+    /// The resources the operation would affect.
+    pub preview: JsonValue,
+    /// Pass back as `confirmToken` (without `dryRun`) to proceed.
+    pub confirm_token: String,
+    pub expires_in_seconds: u64,
+}
+
+/// Result of a destructive tool: either what it did (`T`) or, when it did
+/// not run, a [`DryRun`] preview. Exactly one of the two is present.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Confirmable<T> {
+    #[serde(flatten)]
+    pub done: Option<T>,
+    #[serde(flatten)]
+    pub dry_run: Option<DryRun>,
+}
+
+impl<T> Confirmable<T> {
+    pub fn done(result: T) -> Self {
+        Self {
+            done: Some(result),
+            dry_run: None,
+        }
+    }
+
+    pub fn dry_run(preview: DryRun) -> Self {
+        Self {
+            done: None,
+            dry_run: Some(preview),
+        }
+    }
+}
@@ -16,6 +16,23 @@ pub struct BoardGetSprintsArgs {
     /// State of the sprints (active, future, closed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<SprintState>,
+
+    /// Index of the first sprint (default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<i64>,
+
+    /// Sprints per page (default 50). With `allPages`, caps the total
+    /// number of sprints instead (default and maximum 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+
+    /// Fetch every page instead of one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_pages: Option<bool>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -38,6 +55,10 @@ pub struct SprintCreateArgs {
     /// End date (ISO 8601)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_date: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -65,6 +86,10 @@ pub struct SprintUpdateArgs {
     /// End date (ISO 8601), required when starting a sprint
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_date: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -75,6 +100,10 @@ pub struct SprintAddIssuesArgs {
     
     /// List of issue keys to add
     pub issue_keys: Vec<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -82,4 +111,55 @@ pub struct SprintAddIssuesArgs {
 pub struct SprintDeleteArgs {
     /// Sprint ID to delete
     pub sprint_id: i64,
-}
\ No newline at end of file
+
+    /// Only report what would be affected, without changing anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Token returned by a previous dry run, required to proceed when confirmation is enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_token: Option<String>,
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+/// A sprint as returned by the Agile API.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Sprint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(rename = "self", skip_serializing_if = "Option::is_none")]
+    pub self_link: Option<String>,
+    #[serde(default)]
+    pub name: String,
+    /// `future`, `active` or `closed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complete_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_board_id: Option<i64>,
+}
+
+/// A page of sprints of a board.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintPage {
+    #[serde(default)]
+    pub values: Vec<Sprint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_results: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_last: Option<bool>,
+}
@@ -0,0 +1,21 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoLastArgs {
+    /// Only undo the latest change to this issue (e.g. "PROJ-123").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_key: Option<String>,
+
+    /// Only undo the latest change in this workspace (profile). Defaults to any workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoOperationArgs {
+    /// The 'undoId' returned by issue_edit_details, issue_assign, issue_set_parent or issue_update_status.
+    pub operation_id: u64,
+}
@@ -7,8 +7,12 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 #[schemars(inline)]
 pub struct User {
-    /// The account ID of the user.
+    /// The account ID of the user (empty on Data Center).
+    #[serde(default)]
     pub account_id: String,
+    /// The username of the user (Data Center only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// The account type of the user.
     pub account_type: Option<String>,
     /// The display name of the user.
@@ -30,4 +34,14 @@ pub struct User {
 pub struct ProjectGetUsersArgs {
     /// The project key to find assignable users for (e.g. "PROJ").
     pub project_key: String,
-}
\ No newline at end of file
+
+    /// Workspace (profile) name to run against. Defaults to the primary workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserList {
+    pub users: Vec<User>,
+}
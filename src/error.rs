@@ -0,0 +1,232 @@
+use rmcp::ErrorData;
+use rmcp::model::{Content, IntoContents};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Errors surfaced by the Jira tools.
+///
+/// Every tool returns `Result<_, JiraError>`; rmcp turns the `Err` side into a
+/// `CallToolResult` with `isError: true` whose text is the JSON produced by
+/// [`JiraError::to_json`].
+#[derive(Debug)]
+pub enum JiraError {
+    /// Jira answered with a non-success HTTP status.
+    Http {
+        status: u16,
+        /// Top level `errorMessages` returned by Jira.
+        messages: Vec<String>,
+        /// Per-field `errors` map returned by Jira.
+        errors: BTreeMap<String, String>,
+        /// Raw body, kept when Jira did not send its usual error envelope.
+        body: Option<String>,
+    },
+    /// The request never produced a response (DNS, TLS, timeout, ...).
+    Transport(reqwest::Error),
+    /// The response body could not be decoded into the expected type.
+    Decode(serde_json::Error),
+    /// A name could not be resolved against Jira metadata
+    /// (issue type, transition, field, board, user...).
+    NotFound {
+        kind: &'static str,
+        name: String,
+        hint: Option<String>,
+    },
+    /// The tool arguments are invalid.
+    Validation(String),
+}
+
+impl JiraError {
+    /// Builds an [`JiraError::Http`] from a status code and the raw response body.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let parsed: Option<serde_json::Value> = serde_json::from_str(body).ok();
+        let messages: Vec<String> = parsed
+            .as_ref()
+            .and_then(|v| v.get("errorMessages"))
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|m| m.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let errors: BTreeMap<String, String> = parsed
+            .as_ref()
+            .and_then(|v| v.get("errors"))
+            .and_then(|v| v.as_object())
+            .map(|o| {
+                o.iter()
+                    .map(|(k, v)| {
+                        let msg = v
+                            .as_str()
+                            .map(|s| s.to_string())
+                            .unwrap_or_else(|| v.to_string());
+                        (k.clone(), msg)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let body = if messages.is_empty() && errors.is_empty() && !body.trim().is_empty() {
+            Some(body.to_string())
+        } else {
+            None
+        };
+
+        JiraError::Http {
+            status,
+            messages,
+            errors,
+            body,
+        }
+    }
+
+    pub fn not_found(kind: &'static str, name: impl Into<String>) -> Self {
+        JiraError::NotFound {
+            kind,
+            name: name.into(),
+            hint: None,
+        }
+    }
+
+    pub fn not_found_with_hint(
+        kind: &'static str,
+        name: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        JiraError::NotFound {
+            kind,
+            name: name.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn validation(msg: impl Into<String>) -> Self {
+        JiraError::Validation(msg.into())
+    }
+
+    /// Short machine readable category of the error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JiraError::Http { .. } => "http",
+            JiraError::Transport(_) => "transport",
+            JiraError::Decode(_) => "decode",
+            JiraError::NotFound { .. } => "not_found",
+            JiraError::Validation(_) => "validation",
+        }
+    }
+
+    /// Consistent JSON body returned to MCP clients:
+    /// `{"error": {"kind": ..., "message": ..., ...details}}`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut error = serde_json::json!({
+            "kind": self.kind(),
+            "message": self.to_string(),
+        });
+
+        match self {
+            JiraError::Http {
+                status,
+                messages,
+                errors,
+                body,
+            } => {
+                error["status"] = serde_json::json!(status);
+                if !messages.is_empty() {
+                    error["errorMessages"] = serde_json::json!(messages);
+                }
+                if !errors.is_empty() {
+                    error["errors"] = serde_json::json!(errors);
+                }
+                if let Some(body) = body {
+                    error["body"] = serde_json::json!(body);
+                }
+            }
+            JiraError::NotFound { kind, name, hint } => {
+                error["resource"] = serde_json::json!(kind);
+                error["name"] = serde_json::json!(name);
+                if let Some(hint) = hint {
+                    error["hint"] = serde_json::json!(hint);
+                }
+            }
+            _ => {}
+        }
+
+        serde_json::json!({ "error": error })
+    }
+}
+
+impl fmt::Display for JiraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JiraError::Http {
+                status,
+                messages,
+                errors,
+                body,
+            } => {
+                write!(f, "Jira returned HTTP {}", status)?;
+                let mut details: Vec<String> = messages.clone();
+                details.extend(errors.iter().map(|(k, v)| format!("{}: {}", k, v)));
+                if !details.is_empty() {
+                    write!(f, ": {}", details.join("; "))?;
+                } else if let Some(body) = body {
+                    write!(f, ": {}", body)?;
+                }
+                Ok(())
+            }
+            JiraError::Transport(e) => write!(f, "Request to Jira failed: {}", e),
+            JiraError::Decode(e) => write!(f, "Could not decode Jira response: {}", e),
+            JiraError::NotFound { kind, name, hint } => {
+                write!(f, "{} '{}' not found", kind, name)?;
+                if let Some(hint) = hint {
+                    write!(f, ". {}", hint)?;
+                }
+                Ok(())
+            }
+            JiraError::Validation(msg) => write!(f, "Invalid arguments: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for JiraError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JiraError::Transport(e) => Some(e),
+            JiraError::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for JiraError {
+    fn from(e: reqwest::Error) -> Self {
+        JiraError::Transport(e)
+    }
+}
+
+impl From<serde_json::Error> for JiraError {
+    fn from(e: serde_json::Error) -> Self {
+        JiraError::Decode(e)
+    }
+}
+
+impl IntoContents for JiraError {
+    fn into_contents(self) -> Vec<Content> {
+        vec![Content::text(self.to_json().to_string())]
+    }
+}
+
+/// Protocol error for requests outside tool calls (e.g. `resources/read`),
+/// carrying the same JSON details in `data`.
+impl From<JiraError> for ErrorData {
+    fn from(e: JiraError) -> Self {
+        let data = Some(e.to_json());
+        match e {
+            JiraError::NotFound { .. } | JiraError::Http { status: 404, .. } => {
+                ErrorData::resource_not_found(e.to_string(), data)
+            }
+            JiraError::Validation(_) => ErrorData::invalid_params(e.to_string(), data),
+            _ => ErrorData::internal_error(e.to_string(), data),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::jira::Jira;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Settings of the streamable HTTP transport.
+#[derive(Clone, Debug)]
+pub struct HttpConfig {
+    pub bind: SocketAddr,
+    /// Sessions without traffic for this long are dropped. `None` keeps them
+    /// until the client sends `DELETE` or the server stops.
+    pub session_idle_timeout: Option<Duration>,
+}
+
+impl HttpConfig {
+    pub const DEFAULT_BIND: &str = "127.0.0.1:8080";
+
+    /// Reads `JIRA_HTTP_BIND` and `JIRA_HTTP_SESSION_IDLE_SECS` (default 1800, `0` disables).
+    pub fn from_env() -> anyhow::Result<Self> {
+        let bind = std::env::var("JIRA_HTTP_BIND")
+            .ok()
+            .filter(|b| !b.trim().is_empty())
+            .unwrap_or_else(|| Self::DEFAULT_BIND.to_string());
+        let idle = std::env::var("JIRA_HTTP_SESSION_IDLE_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(1800);
+
+        Ok(Self {
+            bind: Self::parse_bind(&bind)?,
+            session_idle_timeout: (idle > 0).then(|| Duration::from_secs(idle)),
+        })
+    }
+
+    pub fn parse_bind(bind: &str) -> anyhow::Result<SocketAddr> {
+        bind.trim()
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid bind address '{}': {}", bind, e))
+    }
+}
+
+/// Routes of the HTTP transport: MCP on `/mcp` and a `/health` probe.
+/// Every MCP session (`Mcp-Session-Id`) gets its own server instance with
+/// its own undo journal and confirmation tokens; the Jira sites, their
+/// caches and the audit log are shared. Cancelling `shutdown` closes all
+/// sessions.
+pub fn router(jira: Jira, config: &HttpConfig, shutdown: CancellationToken) -> axum::Router {
+    let mut sessions = LocalSessionManager::default();
+    sessions.session_config.keep_alive = config.session_idle_timeout;
+    let sessions = Arc::new(sessions);
+
+    let mcp = StreamableHttpService::new(
+        move || Ok(jira.for_session()),
+        sessions.clone(),
+        StreamableHttpServerConfig {
+            cancellation_token: shutdown,
+            ..Default::default()
+        },
+    );
+
+    axum::Router::new()
+        .route(
+            "/health",
+            axum::routing::get(move || {
+                let sessions = sessions.clone();
+                async move {
+                    axum::Json(serde_json::json!({
+                        "status": "ok",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "sessions": sessions.sessions.read().await.len(),
+                    }))
+                }
+            }),
+        )
+        .nest_service("/mcp", mcp)
+}
+
+/// Serves [`router`] on the configured address until SIGTERM or Ctrl-C.
+pub async fn serve(jira: Jira, config: HttpConfig) -> anyhow::Result<()> {
+    let shutdown = CancellationToken::new();
+    let router = router(jira, &config, shutdown.child_token());
+
+    let listener = tokio::net::TcpListener::bind(config.bind).await?;
+    tracing::info!(
+        "Serving MCP over HTTP at http://{}/mcp",
+        listener.local_addr()?
+    );
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            tracing::info!("Shutting down HTTP transport");
+            // Closes open SSE streams so in-flight connections can drain
+            shutdown.cancel();
+        })
+        .await?;
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Could not listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
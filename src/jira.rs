@@ -1,243 +1,563 @@
+use crate::attachments::AttachmentsDir;
+use crate::audit::{AuditFilter, AuditLog, ToolCall};
+use crate::confirm::Confirmations;
 use crate::domains;
-use reqwest::header::CONTENT_TYPE;
+use crate::domains::enums::FieldPreset;
+use crate::domains::outcome::{Confirmable, DryRun, Outcome};
+use crate::error::JiraError;
+use crate::journal::{Change, Journal, JournalEntry};
+use crate::policy::ToolPolicy;
+use crate::prompts::{self, JiraPrompt};
+use crate::resources::{self, JiraUri};
+use crate::site::{Deployment, Method, Site};
 use rmcp::{
-    ServerHandler,
-    handler::server::{tool::ToolRouter, wrapper},
-    model::{ServerCapabilities, ServerInfo},
-    tool_handler, tool_router,
+    ErrorData, RoleServer, ServerHandler,
+    handler::server::{
+        tool::{ToolCallContext, ToolRouter},
+        wrapper::{self, Json},
+    },
+    model::{
+        CallToolRequestParams, CallToolResult, Content, GetPromptRequestParams, GetPromptResult,
+        IntoContents, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult,
+        ListToolsResult, PaginatedRequestParams, PromptMessage, PromptMessageRole,
+        ReadResourceRequestParams, ReadResourceResult, ResourceContents, ServerCapabilities,
+        ServerInfo,
+    },
+    service::RequestContext,
+    tool_router,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 
-#[derive(Debug)]
+/// Most issues `search_issues` collects with `allPages`.
+const MAX_SEARCH_ISSUES: i32 = 1000;
+
+/// Page size `search_issues` requests with `allPages`.
+const SEARCH_PAGE_SIZE: usize = 100;
+
+/// Largest image `attachment_read` returns; bigger ones are replaced by
+/// their thumbnail.
+const MAX_IMAGE_BYTES: usize = 1024 * 1024;
+
+/// Bytes of a text attachment `attachment_read` returns by default, and
+/// at most.
+const DEFAULT_TEXT_BYTES: usize = 64 * 1024;
+const MAX_TEXT_BYTES: usize = 1024 * 1024;
+
+#[derive(Clone, Debug)]
 pub struct Jira {
     pub tool_router: ToolRouter<Jira>,
-    client: reqwest::Client,
-    workspace: String,
-    username: String,
-    password: String,
-}
-
-#[derive(Clone, Copy, Debug)]
-pub enum Method {
-    Get,
-    Post,
-    Put,
-    Delete,
+    /// Sites by workspace (profile) name.
+    sites: Arc<BTreeMap<String, Arc<Site>>>,
+    /// Workspace used when a tool call does not name one.
+    default_workspace: String,
+    /// Pending dry-run tokens of destructive tools.
+    confirmations: Arc<Confirmations>,
+    /// Where mutating requests are recorded; `None` disables auditing.
+    audit: Option<Arc<AuditLog>>,
+    /// Prior values of reversible edits, for the undo tools.
+    journal: Arc<Journal>,
+    /// Where attachment tools may read and write local files; `None`
+    /// disables local paths.
+    attachments_dir: Option<Arc<AttachmentsDir>>,
 }
 
 #[tool_router]
 impl Jira {
-    pub fn new(workspace: &str, username: &str, password: &str) -> Self {
+    pub fn new(sites: BTreeMap<String, Site>, default_workspace: &str) -> Self {
         let mut tool_router = Self::tool_router();
 
         // Remove $schema to ensure compatibility with Gemini
         for (_, route) in tool_router.map.iter_mut() {
             let map = std::sync::Arc::make_mut(&mut route.attr.input_schema);
             map.remove("$schema");
+            if let Some(output) = route.attr.output_schema.as_mut() {
+                std::sync::Arc::make_mut(output).remove("$schema");
+            }
         }
 
         Self {
             tool_router,
-            client: reqwest::Client::new(),
-            workspace: workspace.to_string(),
-            username: username.to_string(),
-            password: password.to_string(),
+            sites: Arc::new(
+                sites
+                    .into_iter()
+                    .map(|(name, site)| (name, Arc::new(site)))
+                    .collect(),
+            ),
+            default_workspace: default_workspace.to_string(),
+            confirmations: Arc::new(Confirmations::new(false)),
+            audit: None,
+            journal: Arc::new(Journal::new(Journal::DEFAULT_CAPACITY)),
+            attachments_dir: None,
         }
     }
 
-    fn base_url(&self) -> String {
-        format!("https://{}.atlassian.net", self.workspace)
+    /// Records every non-GET request made by a tool call in `log`.
+    pub fn with_audit_log(mut self, log: Option<AuditLog>) -> Self {
+        self.audit = log.map(Arc::new);
+        self
     }
 
-    /// =========================================================================
-    /// HELPERS
-    /// =========================================================================
-    async fn send_request<T, B>(
-        &self,
-        url: &str,
-        method: Method,
-        query_params: Option<&Vec<(&str, String)>>,
-        body: Option<&B>,
-    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>>
-    where
-        T: serde::de::DeserializeOwned,
-        B: serde::Serialize,
-    {
-        let full_url = if url.starts_with("http") {
-            url.to_string()
-        } else {
-            format!("{}{}", self.base_url(), url)
-        };
-
-        let mut req_builder = match method {
-            Method::Get => self.client.get(&full_url),
-            Method::Post => self.client.post(&full_url),
-            Method::Put => self.client.put(&full_url),
-            Method::Delete => self.client.delete(&full_url),
-        };
-
-        req_builder = req_builder
-            .basic_auth(&self.username, Some(&self.password))
-            .header(CONTENT_TYPE, "application/json");
+    /// Lets `issue_attach_file` and `attachment_download` use local paths
+    /// inside `dir`.
+    pub fn with_attachments_dir(mut self, dir: Option<AttachmentsDir>) -> Self {
+        self.attachments_dir = dir.map(Arc::new);
+        self
+    }
 
-        if let Some(params) = query_params {
-            req_builder = req_builder.query(params);
-        }
+    /// When `required`, destructive tools only run with the token returned
+    /// by a dry run of the same call.
+    pub fn with_confirmation_required(mut self, required: bool) -> Self {
+        self.confirmations = Arc::new(Confirmations::new(required));
+        self
+    }
 
-        if let Some(b) = body {
-            req_builder = req_builder.json(b);
+    /// A copy for one client session: the sites, caches and audit log are
+    /// shared, while the undo journal and pending confirmation tokens start
+    /// empty so one session cannot undo or confirm another's operations.
+    pub fn for_session(&self) -> Self {
+        Self {
+            confirmations: Arc::new(Confirmations::new(self.confirmations.required())),
+            journal: Arc::new(Journal::new(Journal::DEFAULT_CAPACITY)),
+            ..self.clone()
         }
+    }
 
-        let resp = req_builder.send().await?;
+    /// Removes the tools `policy` does not permit, so clients never see them.
+    pub fn with_tool_policy(mut self, policy: &ToolPolicy) -> anyhow::Result<Self> {
+        policy.validate(self.tool_router.map.values().map(|route| &route.attr))?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await?;
-            return Err(format!("HTTP {}: {}", status, text).into());
+        let mut removed: Vec<_> = self
+            .tool_router
+            .map
+            .iter()
+            .filter(|(_, route)| !policy.permits(&route.attr))
+            .map(|(name, _)| name.clone())
+            .collect();
+        removed.sort();
+        for name in &removed {
+            self.tool_router.map.remove(name);
         }
-
-        let res_text = resp.text().await?;
-        if res_text.is_empty() || res_text == "null" {
-            return serde_json::from_str("null").map_err(|e| e.into());
+        if !removed.is_empty() {
+            tracing::info!("Tools disabled by policy: {}", removed.join(", "));
         }
+        Ok(self)
+    }
 
-        serde_json::from_str::<T>(&res_text).map_err(|e| e.into())
+    /// =========================================================================
+    /// HELPERS
+    /// =========================================================================
+    fn site(&self, workspace: Option<&str>) -> Result<&Site, JiraError> {
+        let name = workspace
+            .filter(|w| !w.trim().is_empty())
+            .unwrap_or(&self.default_workspace);
+        self.sites.get(name).map(|s| s.as_ref()).ok_or_else(|| {
+            JiraError::not_found_with_hint(
+                "Workspace",
+                name,
+                format!(
+                    "Configured workspaces: {}. Use 'workspace_list' to see them.",
+                    self.sites.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            )
+        })
     }
 
-    async fn find_field_id(&self, name: &str) -> Option<String> {
-        let url = "/rest/api/3/field";
-        let fields: Vec<serde_json::Value> = self
-            .send_request::<Vec<serde_json::Value>, ()>(url, Method::Get, None, None::<&()>)
-            .await
-            .ok()?;
-        for field in fields {
-            if let Some(field_name) = field.get("name").and_then(|n| n.as_str()) {
-                if field_name.eq_ignore_ascii_case(name) {
-                    return field
-                        .get("id")
-                        .and_then(|id| id.as_str())
-                        .map(|s| s.to_string());
-                }
-            }
-        }
-        None
+    /// Name of the workspace `workspace` resolves to, as used by [`Self::site`].
+    fn workspace_name(&self, workspace: Option<&str>) -> String {
+        workspace
+            .filter(|w| !w.trim().is_empty())
+            .unwrap_or(&self.default_workspace)
+            .to_string()
     }
 
-    async fn get_editable_field_id(&self, issue_key: &str, possible_names: &[&str]) -> Option<String> {
-        let url = format!("/rest/api/3/issue/{}/editmeta", issue_key);
-        let meta: serde_json::Value = self
-            .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
-            .await
-            .ok()?;
+    fn success(message: String) -> Result<Json<Outcome>, JiraError> {
+        Ok(Json(Outcome::success(message)))
+    }
 
-        let fields = meta.get("fields")?.as_object()?;
+    /// Like [`Self::success`], with the id to pass to `undo_operation`.
+    fn success_undoable(message: String, undo_id: Option<u64>) -> Result<Json<Outcome>, JiraError> {
+        Ok(Json(Outcome {
+            undo_id,
+            ..Outcome::success(message)
+        }))
+    }
 
-        for (id, field_info) in fields {
-            if let Some(name) = field_info.get("name").and_then(|n| n.as_str()) {
-                for target in possible_names {
-                    if name.eq_ignore_ascii_case(target) {
-                        return Some(id.clone());
-                    }
-                }
+    /// Current values of `fields` on an issue, in the shape needed to put
+    /// them back. Fields the issue does not have are restored as `null`.
+    async fn snapshot_fields(
+        site: &Site,
+        issue_key: &str,
+        fields: impl Iterator<Item = &String>,
+    ) -> Result<Change, JiraError> {
+        let names: Vec<&String> = fields.collect();
+        let url = site.api(&format!("/issue/{}", issue_key));
+        let query = vec![(
+            "fields",
+            names
+                .iter()
+                .map(|n| n.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        )];
+        let issue = site
+            .send_request::<serde_json::Value, ()>(&url, Method::Get, Some(&query), None::<&()>)
+            .await?;
+        let fields = names
+            .into_iter()
+            .map(|name| {
+                let value = issue["fields"].get(name).cloned().unwrap_or_default();
+                (name.clone(), crate::journal::restorable(name, value))
+            })
+            .collect();
+        Ok(Change::Fields { fields })
+    }
+
+    /// Stores the snapshot taken before a successful edit in the undo
+    /// journal. A failed snapshot only costs the ability to undo.
+    fn journal(
+        &self,
+        workspace: Option<&str>,
+        tool: &str,
+        issue_key: &str,
+        snapshot: Result<Change, JiraError>,
+    ) -> Option<u64> {
+        match snapshot {
+            Ok(change) => {
+                Some(
+                    self.journal
+                        .record(&self.workspace_name(workspace), tool, issue_key, change),
+                )
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Could not snapshot {} before {}, undo unavailable: {}",
+                    issue_key,
+                    tool,
+                    e
+                );
+                None
             }
         }
-        None
     }
 
-    async fn resolve_issue_type_id(
-        &self,
-        project_key: &str,
-        issue_type: &str,
-    ) -> Option<(String, bool)> {
-        let url = format!(
-            "/rest/api/3/issue/createmeta?projectKeys={}&expand=projects.issuetypes",
-            project_key
-        );
-        let meta: serde_json::Value = self
-            .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
-            .await
-            .ok()?;
+    /// Restores the values recorded in `entry`.
+    async fn undo(&self, mut entry: JournalEntry) -> Result<Json<Outcome>, JiraError> {
+        if entry.undone {
+            return Err(JiraError::validation(format!(
+                "Operation {} was already undone",
+                entry.id
+            )));
+        }
+        let site = self.site(Some(&entry.workspace))?;
+        let key = &entry.issue_key;
 
-        let projects = meta.get("projects")?.as_array()?;
-        let project = projects
-            .iter()
-            .find(|p| p.get("key").and_then(|k| k.as_str()).unwrap_or("") == project_key)?;
-
-        let types = project.get("issuetypes")?.as_array()?;
-        let target = issue_type;
-
-        for t in types {
-            let name = t.get("name").and_then(|v| v.as_str()).unwrap_or("");
-            let untranslated = t
-                .get("untranslatedName")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let id = t.get("id").and_then(|v| v.as_str()).unwrap_or("");
-
-            // Match by ID, name, or untranslated name (homologs)
-            if id == target || name.eq_ignore_ascii_case(target) || untranslated.eq_ignore_ascii_case(target) {
-                let is_subtask = t.get("subtask").and_then(|v| v.as_bool()).unwrap_or(false);
-                return Some((id.to_string(), is_subtask));
+        match &entry.change {
+            Change::Fields { fields } => {
+                let url = site.api(&format!("/issue/{}", key));
+                let body = serde_json::json!({ "fields": fields });
+                site.send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
+                    .await?;
+            }
+            Change::Assignee { previous } => {
+                let url = site.api(&format!("/issue/{}/assignee", key));
+                let body = Self::assignee_body(site, previous.clone());
+                site.send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
+                    .await?;
+            }
+            Change::Status {
+                previous,
+                previous_id,
+            } => {
+                let transition_id = site
+                    .find_transition_to(key, previous, previous_id.as_deref())
+                    .await?;
+                let url = site.api(&format!("/issue/{}/transitions", key));
+                let body = serde_json::json!({ "transition": { "id": transition_id } });
+                site.send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
+                    .await?;
+                site.cache().editmeta.remove(key);
             }
         }
+        self.journal.mark_undone(entry.id);
 
-        None
+        let message = format!(
+            "Reverted {} on {} (operation {})",
+            entry.tool, key, entry.id
+        );
+        entry.undone = true;
+        Ok(Json(Outcome {
+            operation: Some(entry),
+            ..Outcome::success(message)
+        }))
     }
 
-    async fn find_transition_id(
-        &self,
-        issue_key: &str,
-        target_status: domains::enums::Status,
-    ) -> Option<String> {
-        let url = format!("/rest/api/3/issue/{}/transitions", issue_key);
-        let resp: domains::issue::TransitionResponse = self
-            .send_request::<_, ()>(&url, Method::Get, None, None::<&()>)
-            .await
-            .ok()?;
-
-        let target_name = target_status.to_string(); // e.g. "In Progress"
+    /// `PUT /issue/{key}/assignee` body; Data Center identifies users by
+    /// username instead of accountId.
+    fn assignee_body(site: &Site, user: serde_json::Value) -> serde_json::Value {
+        let id_field = match site.deployment() {
+            Deployment::Cloud => "accountId",
+            Deployment::DataCenter => "name",
+        };
+        serde_json::json!({ id_field: user })
+    }
 
-        for transition in &resp.transitions {
-            if transition.name.eq_ignore_ascii_case(&target_name) {
-                return Some(transition.id.clone());
+    /// Gate in front of destructive tools. Returns the preview to send back
+    /// instead of executing, or `None` when the operation may proceed:
+    /// either no dry run was asked and confirmation is not enforced, or a
+    /// valid token for this exact `operation` was supplied.
+    async fn confirm_destructive(
+        &self,
+        operation: String,
+        dry_run: Option<bool>,
+        confirm_token: Option<String>,
+        preview: impl std::future::Future<Output = Result<serde_json::Value, JiraError>>,
+    ) -> Result<Option<DryRun>, JiraError> {
+        let dry_run = dry_run.unwrap_or(false);
+        if !dry_run {
+            if let Some(token) = confirm_token {
+                self.confirmations.redeem(&token, &operation)?;
+                return Ok(None);
             }
-            if transition.to.name.eq_ignore_ascii_case(&target_name) {
-                return Some(transition.id.clone());
+            if !self.confirmations.required() {
+                return Ok(None);
             }
         }
 
-        let target_category = match target_status {
-            domains::enums::Status::ToDo => "new",
-            domains::enums::Status::InProgress | domains::enums::Status::InReview => {
-                "indeterminate"
-            }
-            domains::enums::Status::Done | domains::enums::Status::Cancelled => "done",
-            domains::enums::Status::Blocked => "indeterminate",
+        let preview = preview.await?;
+        let message = if dry_run {
+            "Dry run: nothing was changed. Call again with confirmToken (and without dryRun) to proceed."
+        } else {
+            "Confirmation required: nothing was changed. Review the preview and call again with confirmToken to proceed."
         };
+        Ok(Some(DryRun {
+            dry_run: true,
+            message: message.to_string(),
+            preview: domains::JsonValue(preview),
+            confirm_token: self.confirmations.issue(&operation),
+            expires_in_seconds: self.confirmations.ttl().as_secs(),
+        }))
+    }
+
+    /// The directory local attachment paths must be in.
+    fn attachments_dir(&self) -> Result<&AttachmentsDir, JiraError> {
+        self.attachments_dir.as_deref().ok_or_else(|| {
+            JiraError::validation(
+                "Local file paths are disabled. Set JIRA_ATTACHMENTS_DIR to allow a directory, or pass contentBase64 instead.",
+            )
+        })
+    }
 
-        for transition in &resp.transitions {
-            if let Some(cat) = &transition.to.status_category {
-                if cat.key.eq_ignore_ascii_case(target_category) {
-                    return Some(transition.id.clone());
+    /// Metadata of an attachment.
+    async fn attachment(
+        site: &Site,
+        attachment_id: &str,
+    ) -> Result<domains::attachment::Attachment, JiraError> {
+        let url = site.api(&format!("/attachment/{}", attachment_id));
+        site.send_request::<_, ()>(&url, Method::Get, None, None::<&()>)
+            .await
+    }
+
+    /// Where the content of an attachment is served. Built from the site's
+    /// base URL rather than taken from the metadata, which names the site
+    /// host even behind a proxy or the OAuth gateway.
+    fn attachment_url(site: &Site, attachment_id: &str) -> String {
+        site.api(&format!("/attachment/content/{}", attachment_id))
+    }
+
+    /// The content of `attachment_read`: the description as structured
+    /// content, followed by the image for images.
+    async fn read_attachment(
+        &self,
+        params: domains::attachment::AttachmentReadArgs,
+    ) -> Result<CallToolResult, JiraError> {
+        use base64::Engine;
+        use domains::attachment::{AttachmentContent, AttachmentKind};
+
+        let site = self.site(params.workspace.as_deref())?;
+        let attachment = Self::attachment(site, &params.attachment_id).await?;
+        let mut content = AttachmentContent {
+            id: attachment.id.clone(),
+            filename: attachment.filename.clone(),
+            kind: AttachmentKind::Text,
+            mime_type: attachment.mime(),
+            size: attachment.size,
+            text: None,
+            truncated: false,
+            thumbnail: false,
+            note: None,
+        };
+
+        if attachment.is_image() {
+            content.kind = AttachmentKind::Image;
+            let fits = attachment
+                .size
+                .is_some_and(|size| size <= MAX_IMAGE_BYTES as u64);
+            let (url, thumbnail) = if fits {
+                (Self::attachment_url(site, &attachment.id), false)
+            } else {
+                (
+                    site.api(&format!("/attachment/thumbnail/{}", attachment.id)),
+                    true,
+                )
+            };
+            let image = match site.download(&url, Some(MAX_IMAGE_BYTES)).await {
+                Err(JiraError::Http { status: 404, .. }) if thumbnail => None,
+                image => Some(image?),
+            };
+            let image = match image {
+                Some((data, _)) if data.len() > MAX_IMAGE_BYTES => {
+                    content.note = Some(format!(
+                        "The image is larger than {} bytes; use attachment_download to save it.",
+                        MAX_IMAGE_BYTES
+                    ));
+                    None
                 }
+                Some((data, content_type)) => {
+                    content.thumbnail = thumbnail;
+                    if thumbnail {
+                        content.mime_type = content_type
+                            .filter(|mime| mime.starts_with("image/"))
+                            .unwrap_or_else(|| "image/png".to_string());
+                    }
+                    Some(base64::engine::general_purpose::STANDARD.encode(data))
+                }
+                None => {
+                    content.note = Some(format!(
+                        "The image is larger than {} bytes and Jira has no thumbnail for it; use attachment_download to save it.",
+                        MAX_IMAGE_BYTES
+                    ));
+                    None
+                }
+            };
+
+            let mut result = CallToolResult::structured(serde_json::to_value(&content)?);
+            if let Some(image) = image {
+                result
+                    .content
+                    .push(Content::image(image, content.mime_type));
             }
-        }
+            return Ok(result);
+        }
+
+        if !attachment.is_text() {
+            return Err(JiraError::validation(format!(
+                "Attachment '{}' ({}) is neither an image nor text. Use attachment_download to save it.",
+                attachment.filename,
+                attachment.mime()
+            )));
+        }
+        let limit = params
+            .max_bytes
+            .unwrap_or(DEFAULT_TEXT_BYTES)
+            .clamp(1, MAX_TEXT_BYTES);
+        let (mut data, _) = site
+            .download(&Self::attachment_url(site, &attachment.id), Some(limit))
+            .await?;
+        content.truncated = data.len() > limit;
+        data.truncate(limit);
+        let mut text = match String::from_utf8(data) {
+            Ok(text) => text,
+            // Cut in the middle of a character
+            Err(e) if e.utf8_error().error_len().is_none() => {
+                let valid = e.utf8_error().valid_up_to();
+                let mut data = e.into_bytes();
+                data.truncate(valid);
+                String::from_utf8(data).unwrap_or_default()
+            }
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        };
+        if content.truncated {
+            text.push_str(&format!(
+                "\n[... truncated after {} bytes{}; raise maxBytes or use attachment_download for the rest]",
+                limit,
+                attachment
+                    .size
+                    .map(|size| format!(" of {}", size))
+                    .unwrap_or_default()
+            ));
+        }
+        content.text = Some(text);
+        Ok(CallToolResult::structured(serde_json::to_value(&content)?))
+    }
+
+    /// Key, summary, status, type and subtasks of an issue for dry runs.
+    async fn preview_issue(site: &Site, issue_key: &str) -> Result<serde_json::Value, JiraError> {
+        let url = site.api(&format!("/issue/{}", issue_key));
+        let query = vec![("fields", "summary,status,issuetype,subtasks".to_string())];
+        let issue: serde_json::Value = site
+            .send_request::<serde_json::Value, ()>(&url, Method::Get, Some(&query), None::<&()>)
+            .await?;
+
+        let fields = &issue["fields"];
+        let subtasks: Vec<_> = fields["subtasks"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t.get("key").cloned())
+            .collect();
+        Ok(serde_json::json!({
+            "key": issue["key"],
+            "summary": fields["summary"],
+            "status": fields["status"]["name"],
+            "issueType": fields["issuetype"]["name"],
+            "subtaskCount": subtasks.len(),
+            "subtasks": subtasks,
+        }))
+    }
 
-        None
+    #[rmcp::tool(
+        name = "workspace_list",
+        description = "Lists the Jira workspaces (sites) this server can operate on. Pass the 'workspace' name to any other tool to target a site other than the default.",
+        annotations(read_only_hint = true)
+    )]
+    async fn workspace_list(
+        &self,
+        _params: wrapper::Parameters<domains::workspace::WorkspaceListArgs>,
+    ) -> Result<Json<domains::workspace::WorkspaceList>, JiraError> {
+        let workspaces = self
+            .sites
+            .iter()
+            .map(|(name, site)| domains::workspace::Workspace {
+                name: name.clone(),
+                base_url: site.base_url().to_string(),
+                deployment: site.deployment(),
+                default: *name == self.default_workspace,
+            })
+            .collect();
+        Ok(Json(domains::workspace::WorkspaceList { workspaces }))
     }
 
-    async fn resolve_assignee(&self, assignee: &str) -> Option<String> {
-        if assignee.eq_ignore_ascii_case("me") {
-            let resp: domains::user::User = self
-                .send_request::<_, ()>("/rest/api/3/myself", Method::Get, None, None::<&()>)
-                .await
-                .ok()?;
-            return Some(resp.account_id);
+    #[rmcp::tool(
+        name = "cache_refresh",
+        description = "Clears the cached field definitions, createmeta (issue types per project) and editmeta of a workspace so the next call downloads them again. Use after fields, screens or issue types change in Jira.",
+        annotations(read_only_hint = true)
+    )]
+    async fn cache_refresh(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::workspace::CacheRefreshArgs>,
+    ) -> Result<Json<domains::workspace::CacheRefreshResult>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let cache = site.cache();
+        let scope = params.scope.unwrap_or(domains::workspace::CacheScope::All);
+        let all = scope == domains::workspace::CacheScope::All;
+
+        let mut cleared = BTreeMap::new();
+        if all || scope == domains::workspace::CacheScope::Fields {
+            cleared.insert("fields".to_string(), cache.fields.clear());
+        }
+        if all || scope == domains::workspace::CacheScope::Createmeta {
+            cleared.insert("createmeta".to_string(), cache.createmeta.clear());
         }
-        if assignee.eq_ignore_ascii_case("unassigned") {
-            return Some("".to_string());
+        if all || scope == domains::workspace::CacheScope::Editmeta {
+            cleared.insert("editmeta".to_string(), cache.editmeta.clear());
         }
-        Some(assignee.to_string())
+
+        Ok(Json(domains::workspace::CacheRefreshResult {
+            success: true,
+            cleared,
+        }))
     }
 
     /// =========================================================================
@@ -246,13 +566,15 @@ impl Jira {
 
     #[rmcp::tool(
         name = "issue_create",
-        description = "Creates an issue in Jira. Use this tool to create Stories, Bugs, Epics, Tasks, and Sub-tasks. It handles complex fields like ADF, priority IDs, and Epic linking automatically."
+        description = "Creates an issue in Jira. Use this tool to create Stories, Bugs, Epics, Tasks, and Sub-tasks. It handles complex fields like ADF, priority IDs, and Epic linking automatically.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_create(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueCreateArgs>,
-    ) -> String {
-        let url = "/rest/api/3/issue";
+    ) -> Result<Json<domains::issue::CreatedIssue>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api("/issue");
         let mut fields = HashMap::new();
 
         fields.insert(
@@ -260,18 +582,9 @@ impl Jira {
             serde_json::json!({ "key": params.project_key }),
         );
 
-        let (issue_type_id, _is_subtask) = match self
+        let (issue_type_id, _is_subtask) = site
             .resolve_issue_type_id(&params.project_key, &params.issue_type)
-            .await
-        {
-            Some(res) => res,
-            None => {
-                return format!(
-                    r#"{{"error": "Could not find valid issue type ID for '{}' in project {}. Use 'list_issue_types' to see available types.\n"}}"#, 
-                    params.issue_type, params.project_key
-                );
-            }
-        };
+            .await?;
         fields.insert(
             "issuetype".to_string(),
             serde_json::json!({ "id": issue_type_id }),
@@ -280,10 +593,7 @@ impl Jira {
         fields.insert("summary".to_string(), serde_json::json!(params.summary));
 
         if let Some(desc) = params.description {
-            fields.insert(
-                "description".to_string(),
-                domains::helpers::text_to_adf(&desc, domains::helpers::AdfStyle::Paragraph).0,
-            );
+            fields.insert("description".to_string(), site.rich_text(&desc).await?);
         }
 
         if let Some(priority) = params.priority {
@@ -309,27 +619,24 @@ impl Jira {
             fields.insert("components".to_string(), serde_json::json!(comps));
         }
 
-        if let Some(sp) = params.story_points {
-            if let Some(sp_field) = self.find_field_id("Story Points").await {
-                fields.insert(sp_field, serde_json::json!(sp));
-            }
+        if let Some(sp) = params.story_points
+            && let Some(sp_field) = site.find_field_id("Story Points").await?
+        {
+            fields.insert(sp_field, serde_json::json!(sp));
         }
 
-        if let Some(sp_estimate) = params.story_point_estimate {
-            if let Some(sp_field) = self.find_field_id("Story point estimate").await {
-                fields.insert(sp_field, serde_json::json!(sp_estimate));
-            }
+        if let Some(sp_estimate) = params.story_point_estimate
+            && let Some(sp_field) = site.find_field_id("Story point estimate").await?
+        {
+            fields.insert(sp_field, serde_json::json!(sp_estimate));
         }
 
         let body = serde_json::json!({ "fields": fields });
 
-        match self
-            .send_request::<domains::issue::CreatedIssue, _>(url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+        let res = site
+            .send_request::<domains::issue::CreatedIssue, _>(&url, Method::Post, None, Some(&body))
+            .await?;
+        Ok(Json(res))
     }
 
     /// =========================================================================
@@ -338,82 +645,113 @@ impl Jira {
 
     #[rmcp::tool(
         name = "issue_update_status",
-        description = "Moves an issue to a new workflow status (Transition)."
+        description = "Moves an issue to a new workflow status (Transition).",
+        annotations(destructive_hint = false)
     )]
     async fn issue_update_status(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueUpdateStatusArgs>,
-    ) -> String {
-        let transition_id = match self
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let transition_id = site
             .find_transition_id(&params.issue_key, params.status)
-            .await
-        {
-            Some(id) => id,
-            None => {
-                return format!(
-                    r#"{{"error": "Transition to '{}' not found for issue {}\n"}}"#, // Added newline for clarity
-                    params.status, params.issue_key
-                );
-            }
-        };
-
-        let url = format!("/rest/api/3/issue/{}/transitions", params.issue_key);
+            .await?;
+
+        let snapshot = async {
+            let url = site.api(&format!("/issue/{}", params.issue_key));
+            let query = vec![("fields", "status".to_string())];
+            let issue = site
+                .send_request::<serde_json::Value, ()>(&url, Method::Get, Some(&query), None::<&()>)
+                .await?;
+            let status = &issue["fields"]["status"];
+            Ok(Change::Status {
+                previous: status["name"].as_str().unwrap_or_default().to_string(),
+                previous_id: status["id"].as_str().map(str::to_string),
+            })
+        }
+        .await;
+
+        let url = site.api(&format!("/issue/{}/transitions", params.issue_key));
         let body = serde_json::json!({ "transition": { "id": transition_id } });
 
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Issue {} moved to {}\n"}}"#, // Added newline for clarity
-                params.issue_key, params.status
-            ),
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
+            .await?;
+        // The edit screen may differ per status
+        site.cache().editmeta.remove(&params.issue_key);
+        let undo_id = self.journal(
+            params.workspace.as_deref(),
+            "issue_update_status",
+            &params.issue_key,
+            snapshot,
+        );
+        Self::success_undoable(
+            format!("Issue {} moved to {}", params.issue_key, params.status),
+            undo_id,
+        )
     }
 
     #[rmcp::tool(
         name = "issue_assign",
-        description = "Assigns the issue to a specific user."
+        description = "Assigns the issue to a specific user.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_assign(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueAssignArgs>,
-    ) -> String {
-        let account_id = match self.resolve_assignee(&params.assignee).await {
-            Some(id) => id,
-            None => {
-                return format!(
-                    r#"{{"error": "Could not resolve assignee '{}'\n"}}"#, // Added newline for clarity
-                    params.assignee
-                );
-            }
-        };
-
-        let url = format!("/rest/api/3/issue/{}/assignee", params.issue_key);
-        let body = serde_json::json!({ "accountId": if account_id.is_empty() { None } else { Some(account_id) } });
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let account_id = site.resolve_assignee(&params.assignee).await?;
+
+        let snapshot = async {
+            let url = site.api(&format!("/issue/{}", params.issue_key));
+            let query = vec![("fields", "assignee".to_string())];
+            let issue = site
+                .send_request::<serde_json::Value, ()>(&url, Method::Get, Some(&query), None::<&()>)
+                .await?;
+            let assignee = &issue["fields"]["assignee"];
+            let previous = match site.deployment() {
+                Deployment::Cloud => assignee["accountId"].clone(),
+                Deployment::DataCenter => assignee["name"].clone(),
+            };
+            Ok(Change::Assignee { previous })
+        }
+        .await;
+
+        let url = site.api(&format!("/issue/{}/assignee", params.issue_key));
+        let body = Self::assignee_body(
+            site,
+            if account_id.is_empty() {
+                serde_json::Value::Null
+            } else {
+                serde_json::json!(account_id)
+            },
+        );
 
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Issue {} assigned to {}\n"}}"#, // Added newline for clarity
-                params.issue_key, params.assignee
-            ),
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
+            .await?;
+        let undo_id = self.journal(
+            params.workspace.as_deref(),
+            "issue_assign",
+            &params.issue_key,
+            snapshot,
+        );
+        Self::success_undoable(
+            format!("Issue {} assigned to {}", params.issue_key, params.assignee),
+            undo_id,
+        )
     }
 
     #[rmcp::tool(
         name = "issue_edit_details",
-        description = "Modifies informational fields of an existing issue."
+        description = "Modifies informational fields of an existing issue.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_edit_details(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueEditDetailsArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issue/{}", params.issue_key);
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}", params.issue_key));
         let mut fields = HashMap::new();
 
         if let Some(summary) = params.summary {
@@ -421,22 +759,15 @@ impl Jira {
         }
 
         if let Some(desc) = params.description {
-            fields.insert(
-                "description".to_string(),
-                domains::helpers::text_to_adf(&desc, domains::helpers::AdfStyle::Paragraph).0,
-            );
+            fields.insert("description".to_string(), site.rich_text(&desc).await?);
         }
 
         if let Some(issue_type) = params.issue_type {
             // Extract project key from issue key (e.g., "PROJ-123" -> "PROJ")
             let project_key = params.issue_key.split('-').next().unwrap_or("");
-            
-            if let Some((id, _)) = self.resolve_issue_type_id(project_key, &issue_type).await {
-                fields.insert(
-                    "issuetype".to_string(),
-                    serde_json::json!({ "id": id }),
-                );
-            }
+
+            let (id, _) = site.resolve_issue_type_id(project_key, &issue_type).await?;
+            fields.insert("issuetype".to_string(), serde_json::json!({ "id": id }));
         }
 
         if let Some(priority) = params.priority {
@@ -458,121 +789,128 @@ impl Jira {
             fields.insert("components".to_string(), serde_json::json!(comps));
         }
 
+        let snapshot = Self::snapshot_fields(site, &params.issue_key, fields.keys()).await;
         let body = serde_json::json!({ "fields": fields });
 
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Issue {} updated successfully\n"}}"#, // Added newline for clarity
-                params.issue_key
-            ),
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
+            .await?;
+        let undo_id = self.journal(
+            params.workspace.as_deref(),
+            "issue_edit_details",
+            &params.issue_key,
+            snapshot,
+        );
+        Self::success_undoable(
+            format!("Issue {} updated successfully", params.issue_key),
+            undo_id,
+        )
     }
 
     #[rmcp::tool(
         name = "issue_set_story_points",
-        description = "Sets the story point estimation for an issue. Automatically detects the correct field (Story Points or Story point estimate)."
+        description = "Sets the story point estimation for an issue. Automatically detects the correct field (Story Points or Story point estimate).",
+        annotations(destructive_hint = false)
     )]
     async fn issue_set_story_points(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueSetStoryPointsArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issue/{}", params.issue_key);
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}", params.issue_key));
         let mut fields = HashMap::new();
 
         // Check which field is actually editable for this issue
-        let field_id = self
+        let field_id = site
             .get_editable_field_id(
                 &params.issue_key,
                 &["Story Points", "Story point estimate"],
             )
-            .await;
+            .await?;
 
         match field_id {
             Some(id) => {
                 fields.insert(id, serde_json::json!(params.story_points));
                 let body = serde_json::json!({ "fields": fields });
 
-                match self
-                    .send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
-                    .await
-                {
-                    Ok(_) => format!(
-                        r#"{{"success": true, "message": "Story points set to {} for issue {}\n"}}"#,
-                        params.story_points, params.issue_key
-                    ),
-                    Err(e) => e.to_string(),
-                }
+                site.send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
+                    .await?;
+                Self::success(format!(
+                    "Story points set to {} for issue {}",
+                    params.story_points, params.issue_key
+                ))
             }
             None => {
                 // Fallback: Try global search if editmeta fails (though unlikely to work if editmeta didn't have it)
-                let legacy_id = self.find_field_id("Story Points").await;
-                let nextgen_id = self.find_field_id("Story point estimate").await;
-                
-                format!(
-                    r#"{{"error": "Could not find an editable 'Story Points' field for issue {}. Detected fields in instance: Story Points (Classic) = {:?}, Story point estimate (Next-Gen) = {:?}. Please ensure the field is on the issue's EDIT screen.\n"}}"#,
-                    params.issue_key, legacy_id, nextgen_id
-                )
+                let legacy_id = site.find_field_id("Story Points").await?;
+                let nextgen_id = site.find_field_id("Story point estimate").await?;
+
+                Err(JiraError::not_found_with_hint(
+                    "Editable field",
+                    "Story Points",
+                    format!(
+                        "Detected fields in instance for issue {}: Story Points (Classic) = {:?}, Story point estimate (Next-Gen) = {:?}. Please ensure the field is on the issue's EDIT screen.",
+                        params.issue_key, legacy_id, nextgen_id
+                    ),
+                ))
             }
         }
     }
 
     #[rmcp::tool(
         name = "issue_add_comment",
-        description = "Adds a comment to an issue."
+        description = "Adds a comment to an issue.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_add_comment(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueAddCommentArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issue/{}/comment", params.issue_key);
-        let body = serde_json::json!({ "body": domains::helpers::text_to_adf(&params.comment, domains::helpers::AdfStyle::Paragraph).0 });
-
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+    ) -> Result<Json<domains::issue::Comment>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}/comment", params.issue_key));
+        let body = serde_json::json!({ "body": site.rich_text(&params.comment).await? });
+
+        let res = site
+            .send_request::<domains::issue::Comment, _>(&url, Method::Post, None, Some(&body))
+            .await?;
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "issue_link",
-        description = "Creates a semantic link between two issues."
+        description = "Creates a semantic link between two issues.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_link(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueLinkArgs>,
-    ) -> String {
-        let url = "/rest/api/3/issueLink";
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api("/issueLink");
         let body = serde_json::json!({
             "type": { "name": params.link_type },
             "inwardIssue": { "key": params.source_issue_key },
             "outwardIssue": { "key": params.target_issue_key }
         });
 
-        match self
-            .send_request::<serde_json::Value, _>(url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Linked {} to {} with type {}\n"}}"#, // Added newline for clarity
-                params.source_issue_key, params.target_issue_key, params.link_type
-            ),
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
+            .await?;
+        Self::success(format!(
+            "Linked {} to {} with type {}",
+            params.source_issue_key, params.target_issue_key, params.link_type
+        ))
     }
 
-    #[rmcp::tool(name = "issue_log_work", description = "Logs time spent on an issue.")]
+    #[rmcp::tool(
+        name = "issue_log_work",
+        description = "Logs time spent on an issue.",
+        annotations(destructive_hint = false)
+    )]
     async fn issue_log_work(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueLogWorkArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issue/{}/worklog", params.issue_key);
+    ) -> Result<Json<domains::issue::Worklog>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}/worklog", params.issue_key));
         let mut body = HashMap::new();
         body.insert(
             "timeSpent".to_string(),
@@ -584,142 +922,236 @@ impl Jira {
         }
 
         if let Some(comment) = params.comment {
-            body.insert(
-                "comment".to_string(),
-                domains::helpers::text_to_adf(&comment, domains::helpers::AdfStyle::Paragraph).0,
-            );
+            body.insert("comment".to_string(), site.rich_text(&comment).await?);
         }
 
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+        let res = site
+            .send_request::<domains::issue::Worklog, _>(&url, Method::Post, None, Some(&body))
+            .await?;
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "issue_delete",
-        description = "Permanently deletes an issue from Jira."
+        description = "Permanently deletes an issue from Jira.",
+        annotations(destructive_hint = true)
     )]
     async fn issue_delete(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueDeleteArgs>,
-    ) -> String {
-        let mut url = format!("/rest/api/3/issue/{}", params.issue_key);
+    ) -> Result<Json<Confirmable<Outcome>>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let operation = format!(
+            "issue_delete {} {} deleteSubtasks={}",
+            site.base_url(),
+            params.issue_key,
+            params.delete_subtasks.unwrap_or(false)
+        );
+        let preview = async {
+            let issue = Self::preview_issue(site, &params.issue_key).await?;
+            let blocked = issue["subtaskCount"].as_u64().unwrap_or(0) > 0
+                && !params.delete_subtasks.unwrap_or(false);
+            let mut preview = serde_json::json!({
+                "issues": [issue],
+                "deleteSubtasks": params.delete_subtasks.unwrap_or(false),
+            });
+            if blocked {
+                preview["warning"] = serde_json::json!(
+                    "The issue has subtasks: Jira rejects the deletion unless deleteSubtasks is true"
+                );
+            }
+            Ok(preview)
+        };
+        if let Some(preview) = self
+            .confirm_destructive(
+                operation,
+                params.dry_run,
+                params.confirm_token.clone(),
+                preview,
+            )
+            .await?
+        {
+            return Ok(Json(Confirmable::dry_run(preview)));
+        }
+
+        let mut url = site.api(&format!("/issue/{}", params.issue_key));
         if let Some(delete_subtasks) = params.delete_subtasks {
             url = format!("{}?deleteSubtasks={}", url, delete_subtasks);
         }
 
-        match self
-            .send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Issue {} deleted successfully\n"}}"#, // Added newline for clarity
-                params.issue_key
-            ),
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
+            .await?;
+        Ok(Json(Confirmable::done(Outcome::success(format!(
+            "Issue {} deleted successfully",
+            params.issue_key
+        )))))
     }
 
     #[rmcp::tool(
         name = "issue_archive",
-        description = "Archives a list of issues. Archiving an issue removes it from the index and search results but preserves the data."
+        description = "Archives a list of issues. Archiving an issue removes it from the index and search results but preserves the data.",
+        annotations(destructive_hint = true)
     )]
     async fn issue_archive(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueArchiveArgs>,
-    ) -> String {
-        let url = "/rest/api/3/issue/archive";
-        let body = serde_json::json!({ "issueIdsOrKeys": params.issue_keys });
-
-        match self
-            .send_request::<serde_json::Value, _>(url, Method::Put, None, Some(&body))
-            .await
+    ) -> Result<Json<Confirmable<domains::issue::ArchiveResult>>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let operation = format!(
+            "issue_archive {} {}",
+            site.base_url(),
+            params.issue_keys.join(",")
+        );
+        let preview = async {
+            let mut issues = Vec::new();
+            let mut errors = serde_json::Map::new();
+            for key in &params.issue_keys {
+                match Self::preview_issue(site, key).await {
+                    Ok(issue) => issues.push(issue),
+                    Err(e) => {
+                        errors.insert(key.clone(), serde_json::json!(e.to_string()));
+                    }
+                }
+            }
+            Ok(serde_json::json!({ "issues": issues, "errors": errors }))
+        };
+        if let Some(preview) = self
+            .confirm_destructive(
+                operation,
+                params.dry_run,
+                params.confirm_token.clone(),
+                preview,
+            )
+            .await?
         {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
+            return Ok(Json(Confirmable::dry_run(preview)));
         }
+
+        if site.deployment() == Deployment::DataCenter {
+            let res = site.archive_each(&params.issue_keys, "archive").await?;
+            return Ok(Json(Confirmable::done(res)));
+        }
+
+        let url = site.api("/issue/archive");
+        let body = serde_json::json!({ "issueIdsOrKeys": params.issue_keys });
+
+        let res = site
+            .send_request::<domains::issue::ArchiveResult, _>(&url, Method::Put, None, Some(&body))
+            .await?;
+        Ok(Json(Confirmable::done(res)))
     }
 
     #[rmcp::tool(
         name = "issue_unarchive",
-        description = "Unarchives (restores) a list of previously archived issues."
+        description = "Unarchives (restores) a list of previously archived issues.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_unarchive(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueUnarchiveArgs>,
-    ) -> String {
-        let url = "/rest/api/3/issue/archive/restore";
+    ) -> Result<Json<domains::issue::ArchiveResult>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        if site.deployment() == Deployment::DataCenter {
+            return Ok(Json(
+                site.archive_each(&params.issue_keys, "restore").await?,
+            ));
+        }
+
+        let url = site.api("/issue/archive/restore");
         let body = serde_json::json!({ "issueIdsOrKeys": params.issue_keys });
 
-        match self
-            .send_request::<serde_json::Value, _>(url, Method::Put, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+        let res = site
+            .send_request::<domains::issue::ArchiveResult, _>(&url, Method::Put, None, Some(&body))
+            .await?;
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "issue_delete_comment",
-        description = "Deletes a specific comment."
+        description = "Deletes a specific comment.",
+        annotations(destructive_hint = true)
     )]
     async fn issue_delete_comment(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueDeleteCommentArgs>,
-    ) -> String {
-        let url = format!(
-            "/rest/api/3/issue/{}/comment/{}",
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!(
+            "/issue/{}/comment/{}",
             params.issue_key, params.comment_id
-        );
-
-        match self
-            .send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Comment {} deleted successfully\n"}}"#, // Added newline for clarity
-                params.comment_id
-            ),
-            Err(e) => e.to_string(),
-        }
+        ));
+
+        site.send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
+            .await?;
+        Self::success(format!(
+            "Comment {} deleted successfully",
+            params.comment_id
+        ))
     }
 
     #[rmcp::tool(
         name = "issue_delete_link",
-        description = "Removes a link between two issues."
+        description = "Removes a link between two issues.",
+        annotations(destructive_hint = true)
     )]
     async fn issue_delete_link(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueDeleteLinkArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issueLink/{}", params.link_id);
-
-        match self
-            .send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
-            .await
+    ) -> Result<Json<Confirmable<Outcome>>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issueLink/{}", params.link_id));
+
+        let operation = format!("issue_delete_link {} {}", site.base_url(), params.link_id);
+        let preview = async {
+            let link: serde_json::Value = site
+                .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
+                .await?;
+            let issue = |side: &str| {
+                serde_json::json!({
+                    "key": link[side]["key"],
+                    "summary": link[side]["fields"]["summary"],
+                })
+            };
+            Ok(serde_json::json!({
+                "link": {
+                    "id": link["id"],
+                    "type": link["type"]["name"],
+                    "inwardIssue": issue("inwardIssue"),
+                    "outwardIssue": issue("outwardIssue"),
+                }
+            }))
+        };
+        if let Some(preview) = self
+            .confirm_destructive(
+                operation,
+                params.dry_run,
+                params.confirm_token.clone(),
+                preview,
+            )
+            .await?
         {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Link {} deleted successfully\n"}}"#, // Added newline for clarity
-                params.link_id
-            ),
-            Err(e) => e.to_string(),
+            return Ok(Json(Confirmable::dry_run(preview)));
         }
+
+        site.send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
+            .await?;
+        Ok(Json(Confirmable::done(Outcome::success(format!(
+            "Link {} deleted successfully",
+            params.link_id
+        )))))
     }
 
     #[rmcp::tool(
         name = "issue_set_parent",
-        description = "Links an existing Story/Task to an Epic, or removes the parent link. Use this to organize existing issues under Epics in your project hierarchy."
+        description = "Links an existing Story/Task to an Epic, or removes the parent link. Use this to organize existing issues under Epics in your project hierarchy.",
+        annotations(destructive_hint = false)
     )]
     async fn issue_set_parent(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueSetParentArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issue/{}", params.issue_key);
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}", params.issue_key));
         let mut fields = HashMap::new();
 
         if params.parent_key.is_empty() {
@@ -731,70 +1163,81 @@ impl Jira {
             );
         }
 
+        let snapshot = Self::snapshot_fields(site, &params.issue_key, fields.keys()).await;
         let body = serde_json::json!({ "fields": fields });
 
-        match self
+        let modern_err = match site
             .send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
             .await
         {
             Ok(_) => {
-                if params.parent_key.is_empty() {
+                let undo_id = self.journal(
+                    params.workspace.as_deref(),
+                    "issue_set_parent",
+                    &params.issue_key,
+                    snapshot,
+                );
+                let message = if params.parent_key.is_empty() {
+                    format!("Parent removed from issue {}", params.issue_key)
+                } else {
+                    format!(
+                        "Issue {} linked to parent {}",
+                        params.issue_key, params.parent_key
+                    )
+                };
+                return Self::success_undoable(message, undo_id);
+            }
+            Err(e) => e,
+        };
+
+        // If modern field fails, try legacy "Epic Link" field
+        let error_msg = modern_err.to_string();
+        if !(error_msg.contains("parent") || error_msg.contains("not found")) {
+            return Err(modern_err);
+        }
+        let Some(epic_link_field) = site.find_field_id("Epic Link").await? else {
+            return Err(modern_err);
+        };
+
+        let mut legacy_fields = HashMap::new();
+        if params.parent_key.is_empty() {
+            legacy_fields.insert(epic_link_field, serde_json::Value::Null);
+        } else {
+            legacy_fields.insert(epic_link_field, serde_json::json!(params.parent_key));
+        }
+        let snapshot = Self::snapshot_fields(site, &params.issue_key, legacy_fields.keys()).await;
+        let legacy_body = serde_json::json!({ "fields": legacy_fields });
+
+        match site
+            .send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&legacy_body))
+            .await
+        {
+            Ok(_) => {
+                let undo_id = self.journal(
+                    params.workspace.as_deref(),
+                    "issue_set_parent",
+                    &params.issue_key,
+                    snapshot,
+                );
+                let message = if params.parent_key.is_empty() {
                     format!(
-                        r#"{{"success": true, "message": "Parent removed from issue {}\n"}}"#,
+                        "Epic link removed from issue {} (legacy field)",
                         params.issue_key
                     )
                 } else {
                     format!(
-                        r#"{{"success": true, "message": "Issue {} linked to parent {}\n"}}"#,
+                        "Issue {} linked to Epic {} (legacy field)",
                         params.issue_key, params.parent_key
                     )
-                }
+                };
+                Self::success_undoable(message, undo_id)
             }
-            Err(e) => {
-                // If modern field fails, try legacy "Epic Link" field
-                let error_msg = e.to_string();
-                if error_msg.contains("parent") || error_msg.contains("not found") {
-                    // Attempt legacy Epic Link field
-                    if let Some(epic_link_field) = self.find_field_id("Epic Link").await {
-                        let mut legacy_fields = HashMap::new();
-                        if params.parent_key.is_empty() {
-                            legacy_fields.insert(epic_link_field, serde_json::Value::Null);
-                        } else {
-                            legacy_fields
-                                .insert(epic_link_field, serde_json::json!(params.parent_key));
-                        }
-                        let legacy_body = serde_json::json!({ "fields": legacy_fields });
-
-                        return match self
-                            .send_request::<serde_json::Value, _>(
-                                &url,
-                                Method::Put,
-                                None,
-                                Some(&legacy_body),
-                            )
-                            .await
-                        {
-                            Ok(_) => {
-                                if params.parent_key.is_empty() {
-                                    format!(
-                                        r#"{{"success": true, "message": "Epic link removed from issue {} (legacy field)\n"}}"#,
-                                        params.issue_key
-                                    )
-                                } else {
-                                    format!(
-                                        r#"{{"success": true, "message": "Issue {} linked to Epic {} (legacy field)\n"}}"#,
-                                        params.issue_key, params.parent_key
-                                    )
-                                }
-                            }
-                            Err(legacy_err) => format!(
-                                r#"{{"error": "Failed with both parent and Epic Link fields. Modern error: {}. Legacy error: {}"}}"#,
-                                error_msg, legacy_err
-                            ),
-                        };
-                    }
-                }
-                error_msg
+            Err(legacy_err) => {
+                tracing::warn!(
+                    "Setting parent failed with both fields. Modern error: {}",
+                    error_msg
+                );
+                Err(legacy_err)
             }
         }
     }
@@ -805,13 +1248,19 @@ impl Jira {
 
     #[rmcp::tool(
         name = "search_issues",
-        description = "Searches for issues using natural language text or specific filters. Use 'filter' parameter to reduce context by 70-90%. Supports same filter syntax as issue_get."
+        description = "Searches for issues using natural language text or specific filters. Use 'filter' parameter to reduce context by 70-90%. Supports same filter syntax as issue_get. Returns one page; pass 'nextPageToken' back as 'pageToken' for the next one, or set 'allPages' to collect up to 1000 issues.",
+        annotations(read_only_hint = true)
     )]
     async fn search_issues(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::jql::SearchIssuesArgs>,
-    ) -> String {
-        let url = "/rest/api/3/search/jql";
+    ) -> Result<Json<domains::jql::SearchPage>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        if params.start_at.is_some() && site.deployment() != Deployment::DataCenter {
+            return Err(JiraError::validation(
+                "startAt is only supported on Jira Data Center. On Cloud, pass the nextPageToken of the previous page as pageToken.",
+            ));
+        }
         let mut jql_parts = Vec::new();
         let mut order_by_clause = None;
 
@@ -822,7 +1271,7 @@ impl Jira {
             jql_parts.push(format!("status = \"{}\"", status));
         }
         if let Some(assignee) = params.assignee {
-            let acc_id = self.resolve_assignee(&assignee).await.unwrap_or(assignee);
+            let acc_id = site.resolve_assignee(&assignee).await?;
             if acc_id.is_empty() {
                 jql_parts.push("assignee is EMPTY".to_string());
             } else {
@@ -846,42 +1295,72 @@ impl Jira {
         let mut jql = jql_parts.join(" AND ");
 
         if let Some(order) = order_by_clause {
-            jql.push_str(" ");
+            jql.push(' ');
             jql.push_str(&order);
         }
 
-        let mut body = HashMap::new();
-        body.insert("jql".to_string(), serde_json::json!(jql));
-        if let Some(limit) = params.limit {
-            body.insert("maxResults".to_string(), serde_json::json!(limit));
-        }
-        body.insert("fieldsByKeys".to_string(), serde_json::json!(true));
-
-        // Agregar filtrado de campos si se especificó
-        if let Some(filter_str) = params.filter {
-            let parsed = domains::helpers::parse_field_filter(&filter_str);
-            let fields: Vec<&str> = parsed.split(',').collect();
-            body.insert("fields".to_string(), serde_json::json!(fields));
-        }
-
-        match self
-            .send_request::<serde_json::Value, _>(url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
+        let fields = params.filter.map(|filter| {
+            domains::helpers::parse_field_filter(&filter)
+                .split(',')
+                .map(str::to_string)
+                .collect()
+        });
+        // startAt is the Data Center page token
+        let page_token = params
+            .page_token
+            .or_else(|| params.start_at.map(|start| start.to_string()));
+
+        let format = params.format.unwrap_or_default();
+        if !params.all_pages.unwrap_or(false) {
+            let mut res = site
+                .search(&jql, fields, params.limit, page_token.as_deref())
+                .await?;
+            res.issues.iter_mut().for_each(|issue| issue.render(format));
+            return Ok(Json(res));
+        }
+
+        let cap = params
+            .limit
+            .unwrap_or(MAX_SEARCH_ISSUES)
+            .clamp(1, MAX_SEARCH_ISSUES) as usize;
+        let mut res = domains::jql::SearchPage {
+            issues: Vec::new(),
+            next_page_token: page_token,
+            is_last: false,
+        };
+        while res.issues.len() < cap {
+            let size = (cap - res.issues.len()).min(SEARCH_PAGE_SIZE) as i32;
+            let page = site
+                .search(
+                    &jql,
+                    fields.clone(),
+                    Some(size),
+                    res.next_page_token.as_deref(),
+                )
+                .await?;
+            let fetched = page.issues.len();
+            res.issues.extend(page.issues);
+            res.next_page_token = page.next_page_token;
+            res.is_last = page.is_last;
+            if res.is_last || fetched == 0 {
+                break;
+            }
         }
+        res.issues.iter_mut().for_each(|issue| issue.render(format));
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "issue_get",
-        description = "Retrieves issue details. Use 'filter' parameter to reduce context by 70-90%. Presets: 'minimal', 'basic', 'standard', 'detailed'. Custom: 'id key summary'. Use 'fields_list' to discover custom fields."
+        description = "Retrieves issue details. Use 'filter' parameter to reduce context by 70-90%. Presets: 'minimal', 'basic', 'standard', 'detailed'. Custom: 'id key summary'. Use 'fields_list' to discover custom fields. Descriptions and comments come as Markdown; set format 'adf' for the raw ADF JSON.",
+        annotations(read_only_hint = true)
     )]
     async fn issue_get(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::IssueGetArgs>,
-    ) -> String {
-        let url = format!("/rest/api/3/issue/{}", params.issue_key);
+    ) -> Result<Json<domains::issue::Issue>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}", params.issue_key));
 
         // Construir query params si hay filtro
         let mut query_params = Vec::new();
@@ -896,118 +1375,120 @@ impl Jira {
             Some(&query_params)
         };
 
-        match self
+        let mut res = site
             .send_request::<domains::issue::Issue, ()>(&url, Method::Get, query, None::<&()>)
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+            .await?;
+        res.render(params.format.unwrap_or_default());
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "fields_list",
-        description = "Lists all available Jira fields for filtering. Returns field IDs, names, types, and whether they're custom fields. Use this once per session to discover which fields you can use in 'filter' parameters of other tools. System fields (summary, status) are standard across all Jira instances. Custom fields (Story Points, Sprint) are specific to this workspace."
+        description = "Lists all available Jira fields for filtering. Returns field IDs, names, types, and whether they're custom fields. Use this once per session to discover which fields you can use in 'filter' parameters of other tools. System fields (summary, status) are standard across all Jira instances. Custom fields (Story Points, Sprint) are specific to this workspace.",
+        annotations(read_only_hint = true)
     )]
     async fn fields_list(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::issue::FieldsListArgs>,
-    ) -> String {
-        let url = "/rest/api/3/field";
+    ) -> Result<Json<domains::issue::FieldList>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let fields = site.fields().await?;
 
-        match self
-            .send_request::<Vec<serde_json::Value>, ()>(url, Method::Get, None, None::<&()>)
-            .await
-        {
-            Ok(fields) => {
-                // Simplificar respuesta para reducir contexto
-                let simplified: Vec<_> = fields
-                    .into_iter()
-                    .filter_map(|field| {
-                        let id = field.get("id")?.as_str()?;
-                        let name = field.get("name")?.as_str()?;
-                        let is_custom = field.get("custom")?.as_bool().unwrap_or(false);
-
-                        // Filtrar por tipo si se especificó
-                        if let Some(ref filter) = params.field_type {
-                            match filter.to_lowercase().as_str() {
-                                "system" if is_custom => return None,
-                                "custom" if !is_custom => return None,
-                                _ => {}
-                            }
-                        }
-
-                        let field_type = field
-                            .get("schema")
-                            .and_then(|s| s.get("type"))
-                            .and_then(|t| t.as_str())
-                            .unwrap_or("unknown");
-
-                        Some(serde_json::json!({
-                            "id": id,
-                            "name": name,
-                            "type": field_type,
-                            "custom": is_custom
-                        }))
-                    })
-                    .collect();
-
-                serde_json::to_string(&serde_json::json!({
-                    "total": simplified.len(),
-                    "fields": simplified,
-                    "usage": "Use field 'id' values in filter parameters. Example: filter='id key summary customfield_10016'"
-                })).unwrap_or_default()
-            }
-            Err(e) => format!(r#"{{"error": "Failed to fetch fields: {}"}}"#, e),
-        }
+        // Simplificar respuesta para reducir contexto
+        let simplified: Vec<_> = fields
+            .iter()
+            .filter_map(|field| {
+                let id = field.get("id")?.as_str()?;
+                let name = field.get("name")?.as_str()?;
+                let is_custom = field.get("custom")?.as_bool().unwrap_or(false);
+
+                // Filtrar por tipo si se especificó
+                if let Some(ref filter) = params.field_type {
+                    match filter.to_lowercase().as_str() {
+                        "system" if is_custom => return None,
+                        "custom" if !is_custom => return None,
+                        _ => {}
+                    }
+                }
+
+                let field_type = field
+                    .get("schema")
+                    .and_then(|s| s.get("type"))
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("unknown");
+
+                Some(domains::issue::FieldSummary {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    field_type: field_type.to_string(),
+                    custom: is_custom,
+                })
+            })
+            .collect();
+
+        Ok(Json(domains::issue::FieldList {
+            total: simplified.len(),
+            fields: simplified,
+            usage: "Use field 'id' values in filter parameters. Example: filter='id key summary customfield_10016'".to_string(),
+        }))
     }
 
     #[rmcp::tool(
         name = "list_issue_types",
-        description = "Lists all available issue types in the Jira instance. Useful for discovering issue type IDs and names when standard English types (Epic, Story, Task, etc.) are not available or use different names."
+        description = "Lists all available issue types in the Jira instance. Useful for discovering issue type IDs and names when standard English types (Epic, Story, Task, etc.) are not available or use different names.",
+        annotations(read_only_hint = true)
     )]
     async fn list_issue_types(
         &self,
-        _params: wrapper::Parameters<domains::issue::ListIssueTypesArgs>,
-    ) -> String {
-        let url = "/rest/api/3/issuetype";
-        match self
-            .send_request::<Vec<domains::issue::IssueTypeDetails>, ()>(url, Method::Get, None, None::<&()>)
-            .await
-        {
-            Ok(types) => serde_json::to_string(&types).unwrap_or_default(),
-            Err(e) => format!(r#"{{"error": "Failed to fetch issue types: {}"}}"#, e),
-        }
+        wrapper::Parameters(params): wrapper::Parameters<domains::issue::ListIssueTypesArgs>,
+    ) -> Result<Json<domains::issue::IssueTypeList>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api("/issuetype");
+        let types = site
+            .send_request::<Vec<domains::issue::IssueTypeDetails>, ()>(
+                &url,
+                Method::Get,
+                None,
+                None::<&()>,
+            )
+            .await?;
+        Ok(Json(domains::issue::IssueTypeList { issue_types: types }))
     }
 
     #[rmcp::tool(
         name = "project_get_users",
-        description = "Returns a list of users assignable to issues in a specific project."
+        description = "Returns a list of users assignable to issues in a specific project.",
+        annotations(read_only_hint = true)
     )]
     async fn project_get_users(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::user::ProjectGetUsersArgs>,
-    ) -> String {
-        let url = "/rest/api/3/user/assignable/search";
+    ) -> Result<Json<domains::user::UserList>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api("/user/assignable/search");
         let query = vec![("project", params.project_key)];
 
-        match self
-            .send_request::<Vec<domains::user::User>, ()>(url, Method::Get, Some(&query), None::<&()>)
-            .await
-        {
-            Ok(users) => serde_json::to_string(&users).unwrap_or_default(),
-            Err(e) => format!(r#"{{"error": "Failed to fetch users: {}"}}"#, e),
-        }
+        let users = site
+            .send_request::<Vec<domains::user::User>, ()>(
+                &url,
+                Method::Get,
+                Some(&query),
+                None::<&()>,
+            )
+            .await?;
+        Ok(Json(domains::user::UserList { users }))
     }
 
     #[rmcp::tool(
         name = "agile_rank_issues",
-        description = "Reorders issues in the backlog or board."
+        description = "Reorders issues in the backlog or board.",
+        annotations(destructive_hint = false)
     )]
     async fn agile_rank_issues(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::agile::AgileRankIssuesArgs>,
-    ) -> String {
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
         let url = "/rest/agile/1.0/issue/rank";
         let mut body = HashMap::new();
         body.insert("issues".to_string(), serde_json::json!(params.issue_keys));
@@ -1019,15 +1500,9 @@ impl Jira {
             body.insert("rankBeforeIssue".to_string(), serde_json::json!(before));
         }
 
-        match self
-            .send_request::<serde_json::Value, _>(url, Method::Put, None, Some(&body))
-            .await
-        {
-            Ok(_) => {
-                r#"{{"success": true, "message": "Issues reordered successfully"}}"#.to_string()
-            }
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, _>(url, Method::Put, None, Some(&body))
+            .await?;
+        Self::success("Issues reordered successfully".to_string())
     }
 
     /// =========================================================================
@@ -1035,45 +1510,17 @@ impl Jira {
     /// =========================================================================
     #[rmcp::tool(
         name = "board_get_sprints",
-        description = "Lists sprints associated with a board or project."
+        description = "Lists sprints associated with a board or project. Returns one page (startAt, limit); set 'allPages' to collect up to 1000 sprints.",
+        annotations(read_only_hint = true)
     )]
     async fn board_get_sprints(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::sprint::BoardGetSprintsArgs>,
-    ) -> String {
-        // First, find the board ID
-        let board_id = if let Some(name) = params.board_name {
-            let url = "/rest/agile/1.0/board";
-            let query = vec![("name", name)];
-            let resp: serde_json::Value = self
-                .send_request::<_, ()>(url, Method::Get, Some(&query), None::<&()>)
-                .await
-                .unwrap_or(serde_json::Value::Null);
-            resp.get("values")
-                .and_then(|v| v.as_array())
-                .and_then(|a| a.first())
-                .and_then(|b| b.get("id"))
-                .and_then(|id| id.as_i64())
-        } else if let Some(project) = params.project_key {
-            let url = "/rest/agile/1.0/board";
-            let query = vec![("projectKeyOrId", project)];
-            let resp: serde_json::Value = self
-                .send_request::<_, ()>(url, Method::Get, Some(&query), None::<&()>)
-                .await
-                .unwrap_or(serde_json::Value::Null);
-            resp.get("values")
-                .and_then(|v| v.as_array())
-                .and_then(|a| a.first())
-                .and_then(|b| b.get("id"))
-                .and_then(|id| id.as_i64())
-        } else {
-            None
-        };
-
-        let board_id = match board_id {
-            Some(id) => id,
-            None => return r#"{{"error": "Board not found"}}"#.to_string(),
-        };
+    ) -> Result<Json<domains::sprint::SprintPage>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let board_id = site
+            .find_board_id(params.board_name, params.project_key)
+            .await?;
 
         let url = format!("/rest/agile/1.0/board/{}/sprint", board_id);
         let mut query = Vec::new();
@@ -1081,72 +1528,60 @@ impl Jira {
             query.push(("state", state.to_string()));
         }
 
-        match self
-            .send_request::<serde_json::Value, ()>(&url, Method::Get, Some(&query), None::<&()>)
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+        let res = site
+            .agile_list(
+                &url,
+                query,
+                params.start_at,
+                params.limit,
+                params.all_pages.unwrap_or(false),
+            )
+            .await?;
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "board_get_backlog",
-        description = "Gets issues in the backlog."
+        description = "Gets issues in the backlog, in rank order. Use 'filter' parameter to reduce context (same syntax as issue_get). Returns one page (startAt, limit); set 'allPages' to collect up to 1000 issues.",
+        annotations(read_only_hint = true)
     )]
     async fn board_get_backlog(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::agile::BoardGetBacklogArgs>,
-    ) -> String {
-        let board_id = if let Some(name) = params.board_name {
-            let url = "/rest/agile/1.0/board";
-            let query = vec![("name", name)];
-            let resp: serde_json::Value = self
-                .send_request::<_, ()>(url, Method::Get, Some(&query), None::<&()>)
-                .await
-                .unwrap_or(serde_json::Value::Null);
-            resp.get("values")
-                .and_then(|v| v.as_array())
-                .and_then(|a| a.first())
-                .and_then(|b| b.get("id"))
-                .and_then(|id| id.as_i64())
-        } else if let Some(project) = params.project_key {
-            let url = "/rest/agile/1.0/board";
-            let query = vec![("projectKeyOrId", project)];
-            let resp: serde_json::Value = self
-                .send_request::<_, ()>(url, Method::Get, Some(&query), None::<&()>)
-                .await
-                .unwrap_or(serde_json::Value::Null);
-            resp.get("values")
-                .and_then(|v| v.as_array())
-                .and_then(|a| a.first())
-                .and_then(|b| b.get("id"))
-                .and_then(|id| id.as_i64())
-        } else {
-            None
-        };
-
-        let board_id = match board_id {
-            Some(id) => id,
-            None => return r#"{{"error": "Board not found"}}"#.to_string(),
-        };
+    ) -> Result<Json<domains::jql::IssuePage>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let board_id = site
+            .find_board_id(params.board_name, params.project_key)
+            .await?;
 
         let url = format!("/rest/agile/1.0/board/{}/backlog", board_id);
-
-        match self
-            .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
+        let mut query = Vec::new();
+        if let Some(filter_str) = params.filter {
+            query.push(("fields", domains::helpers::parse_field_filter(&filter_str)));
         }
+
+        let res = site
+            .agile_list(
+                &url,
+                query,
+                params.start_at,
+                params.limit,
+                params.all_pages.unwrap_or(false),
+            )
+            .await?;
+        Ok(Json(res))
     }
 
-    #[rmcp::tool(name = "sprint_create", description = "Creates a new planned sprint.")]
+    #[rmcp::tool(
+        name = "sprint_create",
+        description = "Creates a new planned sprint.",
+        annotations(destructive_hint = false)
+    )]
     async fn sprint_create(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::sprint::SprintCreateArgs>,
-    ) -> String {
+    ) -> Result<Json<domains::sprint::Sprint>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
         let url = "/rest/agile/1.0/sprint";
         let mut body = HashMap::new();
         body.insert(
@@ -1165,43 +1600,41 @@ impl Jira {
             body.insert("endDate".to_string(), serde_json::json!(end));
         }
 
-        match self
-            .send_request::<serde_json::Value, _>(url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+        let res = site
+            .send_request::<domains::sprint::Sprint, _>(url, Method::Post, None, Some(&body))
+            .await?;
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "sprint_update",
-        description = "Updates a sprint's details or changes its state (Start/Close)."
+        description = "Updates a sprint's details or changes its state (Start/Close).",
+        annotations(destructive_hint = false)
     )]
     async fn sprint_update(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::sprint::SprintUpdateArgs>,
-    ) -> String {
+    ) -> Result<Json<domains::sprint::Sprint>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        // Validate before touching Jira
+        if let Some(new_name) = &params.name
+            && new_name.len() > 30
+        {
+            return Err(JiraError::validation(format!(
+                "Sprint name must be 30 characters or less (got {} characters)",
+                new_name.len()
+            )));
+        }
+
         // First, get current sprint data to merge with updates
         let get_url = format!("/rest/agile/1.0/sprint/{}", params.sprint_id);
-        let current_sprint: serde_json::Value = match self
+        let current_sprint: serde_json::Value = site
             .send_request::<serde_json::Value, ()>(&get_url, Method::Get, None, None::<&()>)
-            .await
-        {
-            Ok(sprint) => sprint,
-            Err(e) => return format!(r#"{{"error": "Failed to get sprint: {}"}}"#, e),
-        };
+            .await?;
 
         let mut body = HashMap::new();
 
-        // Handle name with validation
         let final_name = if let Some(new_name) = params.name {
-            if new_name.len() > 30 {
-                return format!(
-                    r#"{{"error": "Sprint name must be 30 characters or less (got {} characters)"}}"#,
-                    new_name.len()
-                );
-            }
             new_name
         } else {
             // Use current name if not updating
@@ -1243,64 +1676,656 @@ impl Jira {
         }
 
         let url = format!("/rest/agile/1.0/sprint/{}", params.sprint_id);
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Put, None, Some(&body))
-            .await
-        {
-            Ok(res) => serde_json::to_string(&res).unwrap_or_default(),
-            Err(e) => e.to_string(),
-        }
+        let res = site
+            .send_request::<domains::sprint::Sprint, _>(&url, Method::Put, None, Some(&body))
+            .await?;
+        Ok(Json(res))
     }
 
     #[rmcp::tool(
         name = "sprint_add_issues",
-        description = "Moves issues into a specific sprint."
+        description = "Moves issues into a specific sprint.",
+        annotations(destructive_hint = false)
     )]
     async fn sprint_add_issues(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::sprint::SprintAddIssuesArgs>,
-    ) -> String {
+    ) -> Result<Json<Outcome>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
         let url = format!("/rest/agile/1.0/sprint/{}/issue", params.sprint_id);
         let body = serde_json::json!({ "issues": params.issue_keys });
 
-        match self
-            .send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
-            .await
-        {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Issues added to sprint {}\n"}}"#, // Added newline for clarity
-                params.sprint_id
-            ),
-            Err(e) => e.to_string(),
-        }
+        site.send_request::<serde_json::Value, _>(&url, Method::Post, None, Some(&body))
+            .await?;
+        Self::success(format!("Issues added to sprint {}", params.sprint_id))
     }
 
-    #[rmcp::tool(name = "sprint_delete", description = "Deletes a planned sprint.")]
+    #[rmcp::tool(
+        name = "sprint_delete",
+        description = "Deletes a planned sprint.",
+        annotations(destructive_hint = true)
+    )]
     async fn sprint_delete(
         &self,
         wrapper::Parameters(params): wrapper::Parameters<domains::sprint::SprintDeleteArgs>,
-    ) -> String {
+    ) -> Result<Json<Confirmable<Outcome>>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
         let url = format!("/rest/agile/1.0/sprint/{}", params.sprint_id);
 
-        match self
-            .send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
-            .await
+        let operation = format!("sprint_delete {} {}", site.base_url(), params.sprint_id);
+        let preview = async {
+            let sprint: serde_json::Value = site
+                .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
+                .await?;
+            let issues_url = format!("{}/issue", url);
+            let query = vec![("maxResults", "0".to_string())];
+            let issues: serde_json::Value = site
+                .send_request::<serde_json::Value, ()>(
+                    &issues_url,
+                    Method::Get,
+                    Some(&query),
+                    None::<&()>,
+                )
+                .await?;
+            Ok(serde_json::json!({
+                "sprint": {
+                    "id": sprint["id"],
+                    "name": sprint["name"],
+                    "state": sprint["state"],
+                },
+                "issueCount": issues["total"],
+                "note": "Issues of a deleted sprint are moved to the backlog",
+            }))
+        };
+        if let Some(preview) = self
+            .confirm_destructive(
+                operation,
+                params.dry_run,
+                params.confirm_token.clone(),
+                preview,
+            )
+            .await?
         {
-            Ok(_) => format!(
-                r#"{{"success": true, "message": "Sprint {} deleted successfully\n"}}"#, // Added newline for clarity
-                params.sprint_id
-            ),
-            Err(e) => e.to_string(),
+            return Ok(Json(Confirmable::dry_run(preview)));
+        }
+
+        site.send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
+            .await?;
+        Ok(Json(Confirmable::done(Outcome::success(format!(
+            "Sprint {} deleted successfully",
+            params.sprint_id
+        )))))
+    }
+
+    /// =========================================================================
+    /// PHASE 5: Audit & Undo Domain
+    /// =========================================================================
+
+    #[rmcp::tool(
+        name = "audit_log_query",
+        description = "Shows recent changes this server made in Jira, newest first: tool, arguments, affected issue keys, HTTP method, URL and response status of every non-GET request. Filter by tool, issueKey or since.",
+        annotations(read_only_hint = true)
+    )]
+    async fn audit_log_query(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::audit::AuditLogQueryArgs>,
+    ) -> Result<Json<domains::audit::AuditLogPage>, JiraError> {
+        let log = self.audit.as_ref().ok_or_else(|| {
+            JiraError::validation("Audit logging is disabled (JIRA_AUDIT_LOG=off)")
+        })?;
+        if let Some(since) = params.since.as_deref()
+            && chrono::DateTime::parse_from_rfc3339(since).is_err()
+        {
+            return Err(JiraError::validation(format!(
+                "'since' must be an RFC 3339 timestamp such as 2024-05-01T00:00:00Z, got '{}'",
+                since
+            )));
+        }
+
+        let filter = AuditFilter {
+            tool: params.tool,
+            issue_key: params.issue_key,
+            since: params.since,
+            limit: params.limit.unwrap_or(20).clamp(1, 200),
+        };
+        let entries = log.query(&filter).await.map_err(|e| {
+            JiraError::validation(format!(
+                "Could not read audit log {}: {}",
+                log.path().display(),
+                e
+            ))
+        })?;
+        Ok(Json(domains::audit::AuditLogPage {
+            path: log.path().display().to_string(),
+            entries,
+        }))
+    }
+
+    #[rmcp::tool(
+        name = "undo_last",
+        description = "Reverts the most recent issue_edit_details, issue_assign, issue_set_parent or issue_update_status call that has not been undone yet, restoring the previous values (a status change is reversed through the workflow when a transition back exists). Optionally limited to one issue or workspace.",
+        annotations(destructive_hint = false)
+    )]
+    async fn undo_last(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::undo::UndoLastArgs>,
+    ) -> Result<Json<Outcome>, JiraError> {
+        let entry = self
+            .journal
+            .last(params.workspace.as_deref(), params.issue_key.as_deref())
+            .ok_or_else(|| {
+                JiraError::not_found_with_hint(
+                    "Undoable operation",
+                    params.issue_key.as_deref().unwrap_or("any issue"),
+                    "Only edits made by this server since it started can be undone.",
+                )
+            })?;
+        self.undo(entry).await
+    }
+
+    #[rmcp::tool(
+        name = "undo_operation",
+        description = "Reverts a specific change by the 'undoId' returned from issue_edit_details, issue_assign, issue_set_parent or issue_update_status.",
+        annotations(destructive_hint = false)
+    )]
+    async fn undo_operation(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::undo::UndoOperationArgs>,
+    ) -> Result<Json<Outcome>, JiraError> {
+        let entry = self.journal.get(params.operation_id).ok_or_else(|| {
+            JiraError::not_found_with_hint(
+                "Undoable operation",
+                params.operation_id.to_string(),
+                "Only recent edits made by this server since it started can be undone.",
+            )
+        })?;
+        self.undo(entry).await
+    }
+
+    /// =========================================================================
+    /// PHASE 6: Attachments Domain
+    /// =========================================================================
+
+    #[rmcp::tool(
+        name = "issue_attach_file",
+        description = "Attaches a file to an issue, such as a log or a screenshot. Pass either its 'contentBase64' with a 'fileName', or the 'path' of a file inside the attachments directory (JIRA_ATTACHMENTS_DIR, when configured).",
+        annotations(destructive_hint = false)
+    )]
+    async fn issue_attach_file(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::attachment::IssueAttachFileArgs>,
+    ) -> Result<Json<domains::attachment::AttachmentList>, JiraError> {
+        use base64::Engine;
+
+        let site = self.site(params.workspace.as_deref())?;
+        let (file_name, data) = match (&params.path, &params.content_base64) {
+            (Some(path), None) => {
+                let resolved = self.attachments_dir()?.readable(path)?;
+                let data = tokio::fs::read(&resolved)
+                    .await
+                    .map_err(|e| JiraError::validation(format!("Cannot read '{}': {}", path, e)))?;
+                let file_name = params.file_name.clone().or_else(|| {
+                    resolved
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                });
+                (file_name, data)
+            }
+            (None, Some(content)) => {
+                let data = base64::engine::general_purpose::STANDARD
+                    .decode(content.trim())
+                    .map_err(|e| {
+                        JiraError::validation(format!("contentBase64 is not valid base64: {}", e))
+                    })?;
+                (params.file_name.clone(), data)
+            }
+            _ => {
+                return Err(JiraError::validation(
+                    "Pass exactly one of 'path' or 'contentBase64'",
+                ));
+            }
+        };
+        let file_name = file_name
+            .filter(|name| !name.trim().is_empty())
+            .ok_or_else(|| JiraError::validation("'fileName' is required with contentBase64"))?;
+
+        let url = site.api(&format!("/issue/{}/attachments", params.issue_key));
+        let attachments = site.upload(&url, &file_name, data).await?;
+        Ok(Json(domains::attachment::AttachmentList { attachments }))
+    }
+
+    #[rmcp::tool(
+        name = "issue_list_attachments",
+        description = "Lists the files attached to an issue, with their IDs, sizes and types.",
+        annotations(read_only_hint = true)
+    )]
+    async fn issue_list_attachments(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<
+            domains::attachment::IssueListAttachmentsArgs,
+        >,
+    ) -> Result<Json<domains::attachment::AttachmentList>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/issue/{}", params.issue_key));
+        let query = vec![("fields", "attachment".to_string())];
+        let mut issue: serde_json::Value = site
+            .send_request::<serde_json::Value, ()>(&url, Method::Get, Some(&query), None::<&()>)
+            .await?;
+
+        let attachments =
+            serde_json::from_value(issue["fields"]["attachment"].take()).unwrap_or_default();
+        Ok(Json(domains::attachment::AttachmentList { attachments }))
+    }
+
+    #[rmcp::tool(
+        name = "attachment_download",
+        description = "Downloads an attachment to a local file inside the attachments directory (JIRA_ATTACHMENTS_DIR). 'path' is relative to that directory; when it is a directory, the file keeps its name in Jira.",
+        annotations(destructive_hint = false)
+    )]
+    async fn attachment_download(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<
+            domains::attachment::AttachmentDownloadArgs,
+        >,
+    ) -> Result<Json<domains::attachment::AttachmentFile>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let dir = self.attachments_dir()?;
+        let attachment = Self::attachment(site, &params.attachment_id).await?;
+
+        let path = dir.writable(&params.path, &attachment.filename)?;
+        if path.exists() && !params.overwrite.unwrap_or(false) {
+            return Err(JiraError::validation(format!(
+                "'{}' already exists. Pass overwrite: true to replace it.",
+                path.display()
+            )));
+        }
+
+        let (data, content_type) = site
+            .download(&Self::attachment_url(site, &attachment.id), None)
+            .await?;
+        // `create_new` refuses a file or link created since the check above
+        let mut options = tokio::fs::OpenOptions::new();
+        if params.overwrite.unwrap_or(false) {
+            options.write(true).create(true).truncate(true);
+        } else {
+            options.write(true).create_new(true);
+        }
+        let write = async {
+            let mut file = options.open(&path).await?;
+            file.write_all(&data).await?;
+            file.flush().await
+        };
+        write.await.map_err(|e| {
+            JiraError::validation(format!("Cannot write '{}': {}", path.display(), e))
+        })?;
+        Ok(Json(domains::attachment::AttachmentFile {
+            path: path.display().to_string(),
+            filename: attachment.filename,
+            size: data.len() as u64,
+            mime_type: attachment.mime_type.or(content_type),
+        }))
+    }
+
+    #[rmcp::tool(
+        name = "attachment_read",
+        description = "Reads an attachment so it can be inspected: PNG, JPEG, GIF and WebP images are returned as image content (large ones as Jira's thumbnail), text files such as logs, JSON or CSV as text, cut off after maxBytes.",
+        annotations(read_only_hint = true),
+        output_schema = rmcp::handler::server::common::schema_for_output::<domains::attachment::AttachmentContent>()
+            .expect("AttachmentContent is an object")
+    )]
+    async fn attachment_read(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::attachment::AttachmentReadArgs>,
+    ) -> Result<CallToolResult, ErrorData> {
+        // Image content does not fit in Json<T>, so errors are wrapped the
+        // same way by hand
+        Ok(match self.read_attachment(params).await {
+            Ok(result) => result,
+            Err(e) => CallToolResult::error(e.into_contents()),
+        })
+    }
+
+    #[rmcp::tool(
+        name = "attachment_delete",
+        description = "Deletes an attachment from its issue.",
+        annotations(destructive_hint = true)
+    )]
+    async fn attachment_delete(
+        &self,
+        wrapper::Parameters(params): wrapper::Parameters<domains::attachment::AttachmentDeleteArgs>,
+    ) -> Result<Json<Confirmable<Outcome>>, JiraError> {
+        let site = self.site(params.workspace.as_deref())?;
+        let url = site.api(&format!("/attachment/{}", params.attachment_id));
+
+        let operation = format!(
+            "attachment_delete {} {}",
+            site.base_url(),
+            params.attachment_id
+        );
+        let preview = async {
+            let attachment = Self::attachment(site, &params.attachment_id).await?;
+            Ok(serde_json::json!({ "attachment": attachment }))
+        };
+        if let Some(preview) = self
+            .confirm_destructive(
+                operation,
+                params.dry_run,
+                params.confirm_token.clone(),
+                preview,
+            )
+            .await?
+        {
+            return Ok(Json(Confirmable::dry_run(preview)));
         }
+
+        site.send_request::<serde_json::Value, ()>(&url, Method::Delete, None, None::<&()>)
+            .await?;
+        Ok(Json(Confirmable::done(Outcome::success(format!(
+            "Attachment {} deleted successfully",
+            params.attachment_id
+        )))))
+    }
+}
+
+/// =========================================================================
+/// RESOURCES
+/// =========================================================================
+impl Jira {
+    fn resource_enabled(&self, template: &str) -> bool {
+        self.tool_router.has_route(JiraUri::tool(template))
+    }
+}
+
+/// =========================================================================
+/// PROMPTS
+/// =========================================================================
+impl Jira {
+    fn prompt_enabled(&self, name: &str) -> bool {
+        JiraPrompt::tools(name)
+            .iter()
+            .all(|tool| self.tool_router.has_route(tool))
+    }
+
+    /// Fetches the data of `prompt` with the read-only tools and returns
+    /// the instructions followed by that data.
+    async fn prompt_messages(
+        &self,
+        prompt: &JiraPrompt,
+        workspace: Option<String>,
+    ) -> Result<Vec<PromptMessage>, JiraError> {
+        let search = |jql: String, limit: i32| domains::jql::SearchIssuesArgs {
+            text: None,
+            jql: Some(jql),
+            status: None,
+            assignee: None,
+            limit: Some(limit),
+            start_at: None,
+            page_token: None,
+            all_pages: None,
+            filter: Some(FieldPreset::Standard.to_string()),
+            format: None,
+            workspace: workspace.clone(),
+        };
+        let mut messages = vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            prompt.instructions(),
+        )];
+
+        match prompt {
+            JiraPrompt::GroomBacklog { board, project } => {
+                let Json(backlog) = self
+                    .board_get_backlog(wrapper::Parameters(domains::agile::BoardGetBacklogArgs {
+                        board_name: board.clone(),
+                        project_key: project.clone(),
+                        start_at: None,
+                        limit: Some(prompts::MAX_BACKLOG_ISSUES),
+                        all_pages: Some(true),
+                        filter: Some(prompts::BACKLOG_FIELDS.to_string()),
+                        workspace: workspace.clone(),
+                    }))
+                    .await?;
+                messages.push(prompts::data_message(
+                    &format!("Backlog ({} issues)", backlog.issues.len()),
+                    &backlog.issues,
+                )?);
+            }
+            JiraPrompt::TriageBugs { project, days } => {
+                let jql = format!(
+                    "project = \"{}\" AND issuetype = Bug AND statusCategory != Done AND created >= -{}d ORDER BY created DESC",
+                    project, days
+                );
+                let Json(bugs) = self
+                    .search_issues(wrapper::Parameters(search(jql, 50)))
+                    .await?;
+                messages.push(prompts::data_message(
+                    &format!("New bugs ({} issues)", bugs.issues.len()),
+                    &bugs.issues,
+                )?);
+            }
+            JiraPrompt::SprintReview {
+                board,
+                project,
+                sprint,
+            } => {
+                let Json(page) = self
+                    .board_get_sprints(wrapper::Parameters(domains::sprint::BoardGetSprintsArgs {
+                        board_name: board.clone(),
+                        project_key: project.clone(),
+                        state: None,
+                        start_at: None,
+                        limit: None,
+                        all_pages: Some(true),
+                        workspace: workspace.clone(),
+                    }))
+                    .await?;
+                let by_state = |state: &str| {
+                    page.values
+                        .iter()
+                        .rev()
+                        .find(|s| s.state.as_deref() == Some(state))
+                };
+                let chosen = match sprint {
+                    Some(wanted) => page.values.iter().find(|s| {
+                        s.id.map(|id| id.to_string()).as_deref() == Some(wanted.as_str())
+                            || s.name.eq_ignore_ascii_case(wanted)
+                    }),
+                    None => by_state("active").or_else(|| by_state("closed")),
+                }
+                .ok_or_else(|| {
+                    JiraError::not_found(
+                        "Sprint",
+                        sprint
+                            .clone()
+                            .unwrap_or_else(|| "active or closed".to_string()),
+                    )
+                })?;
+                let id = chosen.id.unwrap_or_default();
+                let Json(issues) = self
+                    .search_issues(wrapper::Parameters(search(
+                        format!("sprint = {} ORDER BY status, rank", id),
+                        100,
+                    )))
+                    .await?;
+                messages.push(prompts::data_message("Sprint", chosen)?);
+                messages.push(prompts::data_message(
+                    &format!("Sprint issues ({} issues)", issues.issues.len()),
+                    &issues.issues,
+                )?);
+            }
+            JiraPrompt::BreakEpic { epic } => {
+                let Json(issue) = self
+                    .issue_get(wrapper::Parameters(domains::issue::IssueGetArgs {
+                        issue_key: epic.clone(),
+                        filter: Some(FieldPreset::Detailed.to_string()),
+                        format: None,
+                        workspace: workspace.clone(),
+                    }))
+                    .await?;
+                let Json(children) = self
+                    .search_issues(wrapper::Parameters(search(
+                        format!("parent = \"{}\" ORDER BY rank", epic),
+                        100,
+                    )))
+                    .await?;
+                messages.push(prompts::data_message("Epic", &issue)?);
+                messages.push(prompts::data_message(
+                    &format!("Existing child issues ({} issues)", children.issues.len()),
+                    &children.issues,
+                )?);
+            }
+        }
+        Ok(messages)
     }
 }
 
-#[tool_handler]
 impl ServerHandler for Jira {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
             ..Default::default()
         }
     }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        // Make the tool name and arguments available to the audit log
+        let call = self.audit.clone().map(|log| {
+            ToolCall::new(
+                log,
+                request.name.to_string(),
+                serde_json::Value::Object(request.arguments.clone().unwrap_or_default()),
+            )
+        });
+        let tcc = ToolCallContext::new(self, request, context);
+        match call {
+            Some(call) => call.scope(self.tool_router.call(tcc)).await,
+            None => self.tool_router.call(tcc).await,
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult {
+            tools: self.tool_router.list_all(),
+            meta: None,
+            next_cursor: None,
+        })
+    }
+
+    /// Recently viewed issues of the default workspace, when the tool policy
+    /// enables both searching and reading issues. A failed search lists
+    /// nothing rather than failing, since the templates still work.
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        if !self.resource_enabled("issue") || !self.tool_router.has_route("search_issues") {
+            return Ok(ListResourcesResult::with_all_items(Vec::new()));
+        }
+        let site = self.site(None)?;
+        let recent = resources::recent(site).await.unwrap_or_else(|e| {
+            tracing::warn!("Could not list recently viewed issues: {}", e);
+            Vec::new()
+        });
+        Ok(ListResourcesResult::with_all_items(recent))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resources::templates()
+                .into_iter()
+                .filter(|template| self.resource_enabled(&template.name))
+                .collect(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let (target, workspace) = JiraUri::parse(&request.uri).ok_or_else(|| {
+            ErrorData::resource_not_found(
+                format!(
+                    "Unknown resource '{}': expected jira://issue/{{key}}, jira://sprint/{{id}}, jira://board/{{id}}/backlog or jira://filter/{{id}}",
+                    request.uri
+                ),
+                None,
+            )
+        })?;
+        if !self.resource_enabled(target.template()) {
+            return Err(ErrorData::invalid_params(
+                format!(
+                    "Resource '{}' is unavailable: it needs {}, which the tool policy disables",
+                    request.uri,
+                    JiraUri::tool(target.template())
+                ),
+                None,
+            ));
+        }
+        let site = self.site(workspace.as_deref())?;
+        let content = resources::read(site, &target).await?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri,
+                mime_type: Some(resources::MIME_TYPE.to_string()),
+                text: content.to_string(),
+                meta: None,
+            }],
+        })
+    }
+
+    /// Only the prompts whose tools are enabled by the tool policy.
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult::with_all_items(
+            prompts::list()
+                .into_iter()
+                .filter(|prompt| self.prompt_enabled(&prompt.name))
+                .collect(),
+        ))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let (prompt, workspace) = JiraPrompt::parse(&request.name, request.arguments.as_ref())?;
+        if !self.prompt_enabled(&request.name) {
+            return Err(ErrorData::invalid_params(
+                format!(
+                    "Prompt '{}' is unavailable: it needs {}, which the tool policy disables",
+                    request.name,
+                    JiraPrompt::tools(&request.name).join(", ")
+                ),
+                None,
+            ));
+        }
+        Ok(GetPromptResult {
+            description: prompts::list()
+                .into_iter()
+                .find(|p| p.name == request.name)
+                .and_then(|p| p.description),
+            messages: self.prompt_messages(&prompt, workspace).await?,
+        })
+    }
 }
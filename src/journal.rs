@@ -0,0 +1,119 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// What an operation changed, holding the values from before it ran.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Change {
+    /// Issue fields in the shape `PUT /issue/{key}` accepts.
+    #[serde(rename_all = "camelCase")]
+    Fields { fields: Map<String, Value> },
+    /// Account ID (Cloud) or username (Data Center); `null` when unassigned.
+    #[serde(rename_all = "camelCase")]
+    Assignee { previous: Value },
+    #[serde(rename_all = "camelCase")]
+    Status {
+        previous: String,
+        previous_id: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub id: u64,
+    pub timestamp: String,
+    pub workspace: String,
+    pub tool: String,
+    pub issue_key: String,
+    pub change: Change,
+    pub undone: bool,
+}
+
+/// In-memory history of reversible edits, newest last. The oldest entries
+/// are dropped beyond `capacity`; the history does not survive a restart.
+#[derive(Debug)]
+pub struct Journal {
+    capacity: usize,
+    entries: Mutex<VecDeque<JournalEntry>>,
+    next_id: Mutex<u64>,
+}
+
+impl Journal {
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::new()),
+            next_id: Mutex::new(1),
+        }
+    }
+
+    /// Stores `change` and returns the operation id to undo it with.
+    pub fn record(&self, workspace: &str, tool: &str, issue_key: &str, change: Change) -> u64 {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap_or_else(|e| e.into_inner());
+            let id = *next_id;
+            *next_id += 1;
+            id
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.push_back(JournalEntry {
+            id,
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            workspace: workspace.to_string(),
+            tool: tool.to_string(),
+            issue_key: issue_key.to_string(),
+            change,
+            undone: false,
+        });
+        while entries.len() > self.capacity {
+            entries.pop_front();
+        }
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<JournalEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// Newest entry not undone yet, optionally limited to a workspace and issue.
+    pub fn last(&self, workspace: Option<&str>, issue_key: Option<&str>) -> Option<JournalEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .rev()
+            .filter(|e| !e.undone)
+            .filter(|e| workspace.is_none_or(|w| e.workspace == w))
+            .find(|e| issue_key.is_none_or(|k| e.issue_key.eq_ignore_ascii_case(k)))
+            .cloned()
+    }
+
+    pub fn mark_undone(&self, id: u64) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.undone = true;
+        }
+    }
+}
+
+/// Converts a field value as returned by `GET /issue/{key}` into the shape
+/// `PUT /issue/{key}` accepts.
+pub(crate) fn restorable(field: &str, value: Value) -> Value {
+    match (field, value) {
+        ("issuetype" | "priority", Value::Object(object)) => {
+            serde_json::json!({ "id": object.get("id") })
+        }
+        ("parent", Value::Object(object)) => serde_json::json!({ "key": object.get("key") }),
+        ("components", Value::Array(items)) => items
+            .iter()
+            .map(|c| serde_json::json!({ "id": c.get("id") }))
+            .collect(),
+        (_, value) => value,
+    }
+}
@@ -0,0 +1,25 @@
+//! MCP server exposing Jira Cloud and Data Center operations as tools,
+//! resources and prompts.
+//!
+//! The binary (`main.rs`) wires configuration and transports; everything
+//! else lives here so integration tests can drive [`jira::Jira`] in-process.
+
+pub mod attachments;
+pub mod audit;
+pub mod auth;
+pub mod cache;
+pub mod cli;
+pub mod config;
+pub mod confirm;
+pub mod domains;
+pub mod error;
+pub mod http;
+pub mod jira;
+pub mod journal;
+pub mod mentions;
+pub mod oauth;
+pub mod policy;
+pub mod prompts;
+pub mod resources;
+pub mod retry;
+pub mod site;
@@ -1,5 +1,4 @@
-mod domains;
-mod jira;
+use jira_rs::{attachments, audit, cli, config, confirm, http, jira, oauth, policy, retry};
 use rmcp::ServiceExt;
 
 #[tokio::main]
@@ -11,23 +10,120 @@ async fn main() -> anyhow::Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
-    let workspace = std::env::var("JIRA_WORKSPACE").unwrap_or_else(|e| {
-        tracing::error!("JIRA_WORKSPACE environment variable not set: {}", e);
-        panic!("JIRA_WORKSPACE environment variable not set");
-    });
-    let username = std::env::var("JIRA_USERNAME").unwrap_or_else(|e| {
-        tracing::error!("JIRA_USERNAME environment variable not set: {}", e);
-        panic!("JIRA_USERNAME environment variable not set");
-    });
-    let token = std::env::var("JIRA_TOKEN").unwrap_or_else(|e| {
-        tracing::error!("JIRA_TOKEN environment variable not set: {}", e);
-        panic!("JIRA_TOKEN environment variable not set");
-    });
-
-    let transport = (tokio::io::stdin(), tokio::io::stdout());
-    let jira = jira::Jira::new(&workspace, &username, &token)
-        .serve(transport)
-        .await?;
-    jira.waiting().await?;
+    // `--config PATH` (or JIRA_CONFIG) may appear anywhere on the command line
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let config_path = match args.iter().position(|a| a == "--config") {
+        Some(idx) => {
+            if idx + 1 >= args.len() {
+                anyhow::bail!("--config requires a path");
+            }
+            let path = args.remove(idx + 1);
+            args.remove(idx);
+            Some(std::path::PathBuf::from(path))
+        }
+        None => std::env::var("JIRA_CONFIG")
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .map(std::path::PathBuf::from),
+    };
+
+    // Any other leading word runs one tool from the command line, e.g.
+    // `jira-rs issue get PROJ-1`. Usage errors and help need no connection.
+    let command = match args.first() {
+        Some(first) if first != "login" && !first.starts_with('-') => {
+            let tools = jira::Jira::new(Default::default(), "")
+                .tool_router
+                .list_all();
+            match cli::parse(&tools, &args)? {
+                cli::Command::Help(text) => {
+                    println!("{}", text);
+                    return Ok(());
+                }
+                call => Some(call),
+            }
+        }
+        _ => None,
+    };
+
+    let config_file = config::ConfigFile::load(config_path.as_deref())?;
+    let (profiles, default_profile) = config::load_profiles(config_file.as_ref())?;
+
+    // `jira-rs login [site] [--profile NAME]` runs the one-time OAuth authorization flow
+    if args.first().map(String::as_str) == Some("login") {
+        let mut site = None;
+        let mut profile_name = default_profile.clone();
+        let mut rest = args[1..].iter();
+        while let Some(arg) = rest.next() {
+            match arg.as_str() {
+                "--profile" => {
+                    profile_name = rest
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| anyhow::anyhow!("--profile requires a name"))?
+                }
+                other => site = Some(other.to_string()),
+            }
+        }
+        let config = profiles
+            .iter()
+            .find(|p| p.name == profile_name)
+            .and_then(|p| p.oauth.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Profile '{}' has no OAuth client ID configured (JIRA_OAUTH_CLIENT_ID)",
+                    profile_name
+                )
+            })?;
+        return oauth::login(&config, site.as_deref()).await;
+    }
+
+    // `--transport stdio|http` (or JIRA_TRANSPORT) and `--bind ADDR` (or JIRA_HTTP_BIND)
+    let mut transport = std::env::var("JIRA_TRANSPORT").unwrap_or_else(|_| "stdio".to_string());
+    let mut http_config = http::HttpConfig::from_env()?;
+    let flags = if command.is_some() {
+        &[][..]
+    } else {
+        &args[..]
+    };
+    let mut rest = flags.iter();
+    while let Some(arg) = rest.next() {
+        let mut value = || {
+            rest.next()
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("{} requires a value", arg))
+        };
+        match arg.as_str() {
+            "--transport" => transport = value()?,
+            "--bind" => http_config.bind = http::HttpConfig::parse_bind(&value()?)?,
+            other => anyhow::bail!("Unknown argument: {}", other),
+        }
+    }
+
+    let retry = retry::RetryPolicy::from_env();
+    let sites = config::connect_all(profiles, &retry).await?;
+    if command.is_none() {
+        config::check_credentials(&sites).await;
+    }
+    let jira = jira::Jira::new(sites, &default_profile)
+        .with_tool_policy(&policy::ToolPolicy::from_env())?
+        .with_confirmation_required(confirm::Confirmations::required_from_env())
+        .with_audit_log(audit::AuditLog::from_env())
+        .with_attachments_dir(attachments::AttachmentsDir::from_env()?);
+
+    if let Some(command) = command {
+        if !cli::run(jira, command).await? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    match transport.trim().to_lowercase().as_str() {
+        "stdio" => {
+            let transport = (tokio::io::stdin(), tokio::io::stdout());
+            jira.serve(transport).await?.waiting().await?;
+        }
+        "http" => http::serve(jira, http_config).await?,
+        other => anyhow::bail!("Unknown transport '{}': expected 'stdio' or 'http'", other),
+    }
     Ok(())
 }
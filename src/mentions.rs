@@ -0,0 +1,297 @@
+//! User mentions in rich text. `@displayName`, `@email` and
+//! `[~accountId:ID]` in the text of an ADF document are looked up with the
+//! user search API and replaced with `mention` nodes, so Jira notifies the
+//! people mentioned.
+//!
+//! A display name may span several words ("@Ana Lopez"): the longest name
+//! of a user found for the first word wins. A mention matching several
+//! users is an error listing them; one matching nobody stays plain text.
+
+use crate::domains::user::User;
+use crate::error::JiraError;
+use crate::site::{Method, Site};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Token {
+    AccountId(String),
+    Email(String),
+    Name(String),
+}
+
+/// A mention token in a text node, by char offset.
+#[derive(Debug)]
+struct Found {
+    start: usize,
+    len: usize,
+    token: Token,
+}
+
+/// Replaces the mentions in `doc` with ADF `mention` nodes.
+pub(crate) async fn resolve(site: &Site, doc: &mut Value) -> Result<(), JiraError> {
+    let mut tokens = Vec::new();
+    collect(doc, &mut tokens);
+    if tokens.is_empty() {
+        return Ok(());
+    }
+
+    let mut users: HashMap<Token, Vec<User>> = HashMap::new();
+    for token in tokens {
+        if users.contains_key(&token) {
+            continue;
+        }
+        let found = match &token {
+            Token::AccountId(id) => {
+                let query = vec![("accountId", id.clone())];
+                let user = site
+                    .send_request::<User, ()>(&site.api("/user"), Method::Get, Some(&query), None)
+                    .await?;
+                vec![user]
+            }
+            Token::Email(query) | Token::Name(query) => {
+                let params = vec![("query", query.clone()), ("maxResults", "20".to_string())];
+                let found = site
+                    .send_request::<Vec<User>, ()>(
+                        &site.api("/user/search"),
+                        Method::Get,
+                        Some(&params),
+                        None,
+                    )
+                    .await?;
+                found
+                    .into_iter()
+                    .filter(|user| user.active != Some(false))
+                    .filter(|user| user.account_type.as_deref() != Some("app"))
+                    .collect()
+            }
+        };
+        users.insert(token, found);
+    }
+    replace(doc, &users)
+}
+
+/// Whether the text of `node` can hold mentions: not code, not a link.
+fn is_mentionable(node: &Value) -> bool {
+    node["type"] == "text"
+        && !node["marks"].as_array().is_some_and(|marks| {
+            marks
+                .iter()
+                .any(|mark| mark["type"] == "code" || mark["type"] == "link")
+        })
+}
+
+fn collect(node: &Value, tokens: &mut Vec<Token>) {
+    if node["type"] == "codeBlock" {
+        return;
+    }
+    if is_mentionable(node) {
+        let text: Vec<char> = node["text"].as_str().unwrap_or_default().chars().collect();
+        tokens.extend(find(&text).into_iter().map(|found| found.token));
+    }
+    if let Some(content) = node["content"].as_array() {
+        content.iter().for_each(|child| collect(child, tokens));
+    }
+}
+
+fn replace(node: &mut Value, users: &HashMap<Token, Vec<User>>) -> Result<(), JiraError> {
+    if node["type"] == "codeBlock" {
+        return Ok(());
+    }
+    let Some(content) = node.get_mut("content").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+    let mut replaced = Vec::with_capacity(content.len());
+    for mut child in content.drain(..) {
+        if is_mentionable(&child) {
+            replaced.extend(split(&child, users)?);
+        } else {
+            replace(&mut child, users)?;
+            replaced.push(child);
+        }
+    }
+    *content = replaced;
+    Ok(())
+}
+
+/// Splits a text node around the mentions it contains.
+fn split(node: &Value, users: &HashMap<Token, Vec<User>>) -> Result<Vec<Value>, JiraError> {
+    let text: Vec<char> = node["text"].as_str().unwrap_or_default().chars().collect();
+    let mut nodes = Vec::new();
+    let mut plain = 0;
+    for found in find(&text) {
+        if found.start < plain {
+            continue;
+        }
+        let candidates = users
+            .get(&found.token)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let Some((user, len)) = pick(&found, candidates, &text)? else {
+            continue;
+        };
+        if found.start > plain {
+            nodes.push(text_like(node, &text[plain..found.start]));
+        }
+        let name = user.display_name.clone().unwrap_or_default();
+        nodes.push(serde_json::json!({
+            "type": "mention",
+            "attrs": { "id": user.account_id, "text": format!("@{}", name) },
+        }));
+        plain = found.start + len;
+    }
+    if plain == 0 {
+        return Ok(vec![node.clone()]);
+    }
+    if plain < text.len() {
+        nodes.push(text_like(node, &text[plain..]));
+    }
+    Ok(nodes)
+}
+
+/// A copy of the text node `node` (marks included) holding `text`.
+fn text_like(node: &Value, text: &[char]) -> Value {
+    let mut copy = node.clone();
+    copy["text"] = Value::String(text.iter().collect());
+    copy
+}
+
+/// Chooses the user `found` refers to and how many chars of `text` the
+/// mention covers. `None` leaves the text as it is.
+fn pick<'a>(
+    found: &Found,
+    candidates: &'a [User],
+    text: &[char],
+) -> Result<Option<(&'a User, usize)>, JiraError> {
+    if let Token::Name(word) = &found.token {
+        // "@Ana Lopez": the longest display name following the @. Chars
+        // are compared one by one, since lowercasing a whole string can
+        // change its length ('İ')
+        let after = &text[found.start + 1..];
+        let mut named: Vec<(&User, usize)> = candidates
+            .iter()
+            .filter_map(|user| {
+                let name: Vec<char> = user.display_name.as_deref()?.chars().collect();
+                let matches = name.len() <= after.len()
+                    && name
+                        .iter()
+                        .zip(after)
+                        .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()));
+                let boundary = after.get(name.len()).is_none_or(|c| !c.is_alphanumeric());
+                (matches && boundary).then_some((user, name.len() + 1))
+            })
+            .collect();
+        if let Some(longest) = named.iter().map(|(_, len)| *len).max() {
+            named.retain(|(_, len)| *len == longest);
+            return match named.as_slice() {
+                [(user, len)] => Ok(Some((user, *len))),
+                _ => Err(ambiguous(word, named.iter().map(|(user, _)| *user))),
+            };
+        }
+        return match candidates {
+            [] => Ok(None),
+            [user] => Ok(Some((user, found.len))),
+            _ => Err(ambiguous(word, candidates.iter())),
+        };
+    }
+
+    match candidates {
+        [] => Ok(None),
+        [user] => Ok(Some((user, found.len))),
+        _ => {
+            // Several users for an email: keep the one that has it
+            let Token::Email(email) = &found.token else {
+                unreachable!("account IDs resolve to one user");
+            };
+            let exact: Vec<&User> = candidates
+                .iter()
+                .filter(|user| {
+                    user.email_address
+                        .as_deref()
+                        .is_some_and(|address| address.eq_ignore_ascii_case(email))
+                })
+                .collect();
+            match exact.as_slice() {
+                [user] => Ok(Some((user, found.len))),
+                _ => Err(ambiguous(email, candidates.iter())),
+            }
+        }
+    }
+}
+
+fn ambiguous<'a>(mention: &str, users: impl Iterator<Item = &'a User>) -> JiraError {
+    let users: Vec<String> = users
+        .map(|user| {
+            let email = user
+                .email_address
+                .as_deref()
+                .map(|email| format!(" <{}>", email))
+                .unwrap_or_default();
+            format!(
+                "{}{} (accountId {})",
+                user.display_name.as_deref().unwrap_or("?"),
+                email,
+                user.account_id
+            )
+        })
+        .collect();
+    JiraError::validation(format!(
+        "'@{}' matches several users: {}. Mention one by full name, email or as [~accountId:ID].",
+        mention,
+        users.join(", ")
+    ))
+}
+
+/// The mention tokens in `text`: `[~accountId:ID]`, and `@email` or
+/// `@name` at the start of a word.
+fn find(text: &[char]) -> Vec<Found> {
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        let rest: String = text[i..].iter().take(12).collect();
+        if rest.to_lowercase() == "[~accountid:"
+            && let Some(close) = text[i..].iter().position(|c| *c == ']')
+        {
+            let id: String = text[i + 12..i + close].iter().collect();
+            if !id.trim().is_empty() {
+                found.push(Found {
+                    start: i,
+                    len: close + 1,
+                    token: Token::AccountId(id.trim().to_string()),
+                });
+                i += close + 1;
+                continue;
+            }
+        }
+
+        let starts_word = i == 0 || text[i - 1].is_whitespace() || "([{\"'".contains(text[i - 1]);
+        if text[i] == '@' && starts_word {
+            let word: String = text[i + 1..]
+                .iter()
+                .take_while(|c| c.is_alphanumeric() || "._-+@%".contains(**c))
+                .collect();
+            // A sentence may end right after the mention
+            let word = word.trim_end_matches(['.', '-', '_']);
+            let token = match word.split_once('@') {
+                Some((local, domain)) if !local.is_empty() && domain.contains('.') => {
+                    Some(Token::Email(word.to_string()))
+                }
+                Some(_) => None,
+                None if !word.is_empty() => Some(Token::Name(word.to_string())),
+                None => None,
+            };
+            if let Some(token) = token {
+                let len = word.chars().count() + 1;
+                found.push(Found {
+                    start: i,
+                    len,
+                    token,
+                });
+                i += len;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    found
+}
@@ -0,0 +1,324 @@
+use crate::error::JiraError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::RwLock;
+
+const AUTHORIZE_URL: &str = "https://auth.atlassian.com/authorize";
+const TOKEN_URL: &str = "https://auth.atlassian.com/oauth/token";
+const RESOURCES_URL: &str = "https://api.atlassian.com/oauth/token/accessible-resources";
+const DEFAULT_REDIRECT_URI: &str = "http://localhost:8765/callback";
+const DEFAULT_SCOPES: &str = "read:jira-work write:jira-work read:jira-user offline_access \
+    read:board-scope:jira-software read:sprint:jira-software write:sprint:jira-software \
+    delete:sprint:jira-software read:issue:jira-software write:board-scope:jira-software";
+
+/// Client registration of the OAuth 2.0 (3LO) app, read from the environment.
+#[derive(Clone, Debug)]
+pub struct OAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: String,
+    pub token_file: PathBuf,
+}
+
+impl OAuthConfig {
+    /// Reads `{prefix}OAUTH_CLIENT_ID`, `{prefix}OAUTH_CLIENT_SECRET` and the optional
+    /// `{prefix}OAUTH_REDIRECT_URI`, `{prefix}OAUTH_SCOPES` and `{prefix}OAUTH_TOKEN_FILE`,
+    /// where `prefix` is `JIRA_` or `JIRA_<PROFILE>_`.
+    /// Returns `None` when no client ID is configured.
+    pub fn from_env(prefix: &str, profile: &str) -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(format!("{}{}", prefix, name))
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+        };
+        let client_id = var("OAUTH_CLIENT_ID")?;
+
+        Some(Self {
+            client_id,
+            client_secret: var("OAUTH_CLIENT_SECRET").unwrap_or_default(),
+            redirect_uri: var("OAUTH_REDIRECT_URI")
+                .unwrap_or_else(|| DEFAULT_REDIRECT_URI.to_string()),
+            scopes: var("OAUTH_SCOPES").unwrap_or_else(|| DEFAULT_SCOPES.to_string()),
+            token_file: var("OAUTH_TOKEN_FILE")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| default_token_file(profile)),
+        })
+    }
+}
+
+fn default_token_file(profile: &str) -> PathBuf {
+    let file = if profile == crate::config::DEFAULT_PROFILE {
+        "oauth.json".to_string()
+    } else {
+        format!("oauth-{}.json", profile)
+    };
+    crate::config::config_dir().join(file)
+}
+
+/// Tokens persisted between runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredTokens {
+    pub cloud_id: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Unix timestamp (seconds) after which the access token is expired.
+    pub expires_at: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: i64,
+}
+
+/// An authorized OAuth session: holds the current tokens and refreshes them
+/// (rotating the stored refresh token) when they expire or Jira answers 401.
+#[derive(Debug)]
+pub struct OAuthSession {
+    config: OAuthConfig,
+    tokens: RwLock<StoredTokens>,
+}
+
+impl OAuthSession {
+    /// Loads the tokens saved by `jira-rs login`.
+    pub fn load(config: OAuthConfig) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(&config.token_file).map_err(|e| {
+            anyhow::anyhow!(
+                "Could not read OAuth token file {}: {}. Run `jira-rs login` first.",
+                config.token_file.display(),
+                e
+            )
+        })?;
+        let tokens: StoredTokens = serde_json::from_str(&raw)?;
+        Ok(Self {
+            config,
+            tokens: RwLock::new(tokens),
+        })
+    }
+
+    /// API gateway URL of the authorized site; OAuth tokens are not accepted
+    /// on `*.atlassian.net`.
+    pub async fn base_url(&self) -> String {
+        format!(
+            "https://api.atlassian.com/ex/jira/{}",
+            self.tokens.read().await.cloud_id
+        )
+    }
+
+    /// Current access token, refreshed first if it is about to expire.
+    pub async fn access_token(&self, client: &reqwest::Client) -> Result<String, JiraError> {
+        {
+            let tokens = self.tokens.read().await;
+            if tokens.expires_at > chrono::Utc::now().timestamp() + 60 {
+                return Ok(tokens.access_token.clone());
+            }
+        }
+        self.refresh(client).await
+    }
+
+    /// Exchanges the refresh token for a new access token and persists the result.
+    pub async fn refresh(&self, client: &reqwest::Client) -> Result<String, JiraError> {
+        let mut tokens = self.tokens.write().await;
+        let body = serde_json::json!({
+            "grant_type": "refresh_token",
+            "client_id": self.config.client_id,
+            "client_secret": self.config.client_secret,
+            "refresh_token": tokens.refresh_token,
+        });
+        let resp = request_token(client, &body).await?;
+
+        tokens.access_token = resp.access_token;
+        if let Some(refresh_token) = resp.refresh_token {
+            tokens.refresh_token = refresh_token;
+        }
+        tokens.expires_at = chrono::Utc::now().timestamp() + resp.expires_in;
+
+        if let Err(e) = save_tokens(&self.config.token_file, &tokens) {
+            tracing::warn!("Could not persist refreshed OAuth tokens: {}", e);
+        }
+        tracing::debug!("OAuth access token refreshed");
+        Ok(tokens.access_token.clone())
+    }
+}
+
+async fn request_token(
+    client: &reqwest::Client,
+    body: &serde_json::Value,
+) -> Result<TokenResponse, JiraError> {
+    let resp = client.post(TOKEN_URL).json(body).send().await?;
+    let status = resp.status().as_u16();
+    let text = resp.text().await?;
+    if !(200..300).contains(&status) {
+        return Err(JiraError::from_response(status, &text));
+    }
+    serde_json::from_str(&text).map_err(JiraError::from)
+}
+
+fn save_tokens(path: &PathBuf, tokens: &StoredTokens) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // Created private, so the refresh token is never readable by others
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    // A file left by an older version may still be world-readable
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::io::Write::write_all(&mut file, serde_json::to_string_pretty(tokens)?.as_bytes())
+}
+
+/// One-time interactive login (`jira-rs login`): prints the consent URL,
+/// waits for the redirect on `redirect_uri`, exchanges the code and stores
+/// the tokens together with the cloud ID of the chosen site.
+///
+/// `site` selects the site when the account can access several of them
+/// (matched against the site URL or name, e.g. `mycompany`).
+pub async fn login(config: &OAuthConfig, site: Option<&str>) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let state: String = {
+        use rand::Rng;
+        rand::rng()
+            .sample_iter(rand::distr::Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect()
+    };
+
+    let authorize_url = format!(
+        "{}?audience=api.atlassian.com&client_id={}&scope={}&redirect_uri={}&state={}&response_type=code&prompt=consent",
+        AUTHORIZE_URL,
+        urlencoding::encode(&config.client_id),
+        urlencoding::encode(&config.scopes),
+        urlencoding::encode(&config.redirect_uri),
+        state
+    );
+
+    let redirect = reqwest::Url::parse(&config.redirect_uri)?;
+    let port = redirect.port_or_known_default().unwrap_or(80);
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+
+    eprintln!(
+        "Open this URL in your browser to authorize jira-rs:\n\n{}\n",
+        authorize_url
+    );
+    eprintln!("Waiting for the redirect on {} ...", config.redirect_uri);
+
+    let code = loop {
+        let (mut socket, _) = listener.accept().await?;
+        let mut buf = vec![0u8; 8192];
+        let n = socket.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..n]);
+        let target = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("");
+        let callback = reqwest::Url::parse(&format!("http://localhost{}", target))?;
+        if callback.path() != redirect.path() {
+            socket
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await?;
+            continue;
+        }
+
+        let param = |name: &str| {
+            callback
+                .query_pairs()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.into_owned())
+        };
+        let (status, message, outcome) = match (param("code"), param("state"), param("error")) {
+            (_, _, Some(error)) => (
+                "400 Bad Request",
+                "Authorization was denied. You can close this window.",
+                Err(anyhow::anyhow!("Authorization failed: {}", error)),
+            ),
+            (Some(code), Some(returned), None) if returned == state => (
+                "200 OK",
+                "jira-rs is authorized. You can close this window.",
+                Ok(code),
+            ),
+            _ => (
+                "400 Bad Request",
+                "Invalid authorization response. You can close this window.",
+                Err(anyhow::anyhow!(
+                    "Invalid authorization response (state mismatch)"
+                )),
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            message.len(),
+            message
+        );
+        socket.write_all(response.as_bytes()).await?;
+        break outcome?;
+    };
+
+    let token = request_token(
+        &client,
+        &serde_json::json!({
+            "grant_type": "authorization_code",
+            "client_id": config.client_id,
+            "client_secret": config.client_secret,
+            "code": code,
+            "redirect_uri": config.redirect_uri,
+        }),
+    )
+    .await?;
+    let refresh_token = token.refresh_token.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No refresh token returned; make sure the 'offline_access' scope is requested"
+        )
+    })?;
+
+    let resources: Vec<serde_json::Value> = client
+        .get(RESOURCES_URL)
+        .bearer_auth(&token.access_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let resource = resources
+        .iter()
+        .find(|r| match site {
+            Some(site) => ["url", "name"].iter().any(|k| {
+                r.get(*k)
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| v.contains(site))
+            }),
+            None => true,
+        })
+        .ok_or_else(|| anyhow::anyhow!("No accessible Jira site matches {:?}", site))?;
+    let cloud_id = resource
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Accessible resource without an id"))?;
+
+    let tokens = StoredTokens {
+        cloud_id: cloud_id.to_string(),
+        access_token: token.access_token,
+        refresh_token,
+        expires_at: chrono::Utc::now().timestamp() + token.expires_in,
+    };
+    save_tokens(&config.token_file, &tokens)?;
+
+    eprintln!(
+        "Authorized {} ({}). Tokens saved to {}",
+        resource.get("url").and_then(|v| v.as_str()).unwrap_or(""),
+        cloud_id,
+        config.token_file.display()
+    );
+    Ok(())
+}
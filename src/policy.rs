@@ -0,0 +1,112 @@
+use rmcp::model::Tool;
+
+/// Which tools the server exposes, read from the environment.
+///
+/// Patterns in the allow and deny lists are tool names, globs such as
+/// `sprint_*`, or one of the groups derived from the tool annotations:
+/// `read` (tools that never modify Jira), `write` (every other tool) and
+/// `destructive` (deletions and archiving).
+#[derive(Clone, Debug, Default)]
+pub struct ToolPolicy {
+    /// Strip every tool that modifies Jira.
+    pub read_only: bool,
+    /// When not empty, only tools matching one of these patterns are kept.
+    pub allow: Vec<String>,
+    /// Tools matching one of these patterns are removed, even if allowed.
+    pub deny: Vec<String>,
+}
+
+impl ToolPolicy {
+    /// Reads `JIRA_READ_ONLY`, `JIRA_TOOLS_ALLOW` and `JIRA_TOOLS_DENY`
+    /// (comma separated patterns).
+    pub fn from_env() -> Self {
+        let list = |name: &str| {
+            std::env::var(name)
+                .unwrap_or_default()
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect()
+        };
+        Self {
+            read_only: std::env::var("JIRA_READ_ONLY")
+                .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            allow: list("JIRA_TOOLS_ALLOW"),
+            deny: list("JIRA_TOOLS_DENY"),
+        }
+    }
+
+    pub fn permits(&self, tool: &Tool) -> bool {
+        if self.read_only && !is_read_only(tool) {
+            return false;
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| matches(p, tool)) {
+            return false;
+        }
+        !self.deny.iter().any(|p| matches(p, tool))
+    }
+
+    /// Fails on patterns that match none of `tools`, which are most likely typos.
+    pub fn validate<'a>(
+        &self,
+        tools: impl Iterator<Item = &'a Tool> + Clone,
+    ) -> anyhow::Result<()> {
+        for pattern in self.allow.iter().chain(&self.deny) {
+            if !tools.clone().any(|t| matches(pattern, t)) {
+                anyhow::bail!(
+                    "Tool pattern '{}' in JIRA_TOOLS_ALLOW/JIRA_TOOLS_DENY matches no tool",
+                    pattern
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_read_only(tool: &Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .and_then(|a| a.read_only_hint)
+        .unwrap_or(false)
+}
+
+/// MCP treats write tools as destructive unless they say otherwise.
+fn is_destructive(tool: &Tool) -> bool {
+    !is_read_only(tool)
+        && tool
+            .annotations
+            .as_ref()
+            .and_then(|a| a.destructive_hint)
+            .unwrap_or(true)
+}
+
+fn matches(pattern: &str, tool: &Tool) -> bool {
+    match pattern.to_lowercase().as_str() {
+        "read" => is_read_only(tool),
+        "write" => !is_read_only(tool),
+        "destructive" => is_destructive(tool),
+        pattern => glob(pattern, &tool.name),
+    }
+}
+
+/// Matches `name` against a pattern where `*` stands for any run of characters.
+fn glob(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: exact match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -0,0 +1,258 @@
+//! MCP prompts: templates for common Jira workflows. Each prompt fetches
+//! the data it talks about with the read-only tools and embeds it in the
+//! messages, so the assistant starts with the backlog, bugs, sprint or
+//! epic already in context.
+//!
+//! | Prompt          | Tools used                             |
+//! |-----------------|----------------------------------------|
+//! | `groom_backlog` | `board_get_backlog`                    |
+//! | `triage_bugs`   | `search_issues`                        |
+//! | `sprint_review` | `board_get_sprints`, `search_issues`   |
+//! | `break_epic`    | `issue_get`, `search_issues`           |
+
+use crate::error::JiraError;
+use rmcp::model::{JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole};
+use serde::Serialize;
+
+/// Days of bugs `triage_bugs` looks back when `days` is not given.
+const DEFAULT_TRIAGE_DAYS: u32 = 7;
+
+/// Backlog issues embedded by `groom_backlog`, from the top.
+pub const MAX_BACKLOG_ISSUES: i32 = 200;
+
+/// Fields of the backlog issues embedded by `groom_backlog`.
+pub const BACKLOG_FIELDS: &str =
+    "summary,status,assignee,priority,created,updated,issuetype,labels";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JiraPrompt {
+    GroomBacklog {
+        board: Option<String>,
+        project: Option<String>,
+    },
+    TriageBugs {
+        project: String,
+        days: u32,
+    },
+    SprintReview {
+        board: Option<String>,
+        project: Option<String>,
+        sprint: Option<String>,
+    },
+    BreakEpic {
+        epic: String,
+    },
+}
+
+impl JiraPrompt {
+    pub const NAMES: [&'static str; 4] = [
+        "groom_backlog",
+        "triage_bugs",
+        "sprint_review",
+        "break_epic",
+    ];
+
+    /// Reads the prompt `name` and its string arguments, returning the
+    /// prompt and the workspace it should run against.
+    pub fn parse(
+        name: &str,
+        arguments: Option<&JsonObject>,
+    ) -> Result<(Self, Option<String>), JiraError> {
+        let empty = JsonObject::new();
+        let arguments = arguments.unwrap_or(&empty);
+        let optional = |key: &str| {
+            arguments
+                .get(key)
+                .and_then(|v| match v {
+                    serde_json::Value::String(s) => Some(s.trim().to_string()),
+                    serde_json::Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                })
+                .filter(|s| !s.is_empty())
+        };
+        let required = |key: &str| {
+            optional(key).ok_or_else(|| {
+                JiraError::validation(format!("Prompt '{}' requires the '{}' argument", name, key))
+            })
+        };
+
+        let prompt = match name {
+            "groom_backlog" => Self::GroomBacklog {
+                board: optional("board"),
+                project: optional("project"),
+            },
+            "triage_bugs" => Self::TriageBugs {
+                project: required("project")?,
+                days: match optional("days") {
+                    Some(days) => days.parse().map_err(|_| {
+                        JiraError::validation(format!(
+                            "'days' must be a whole number of days, got '{}'",
+                            days
+                        ))
+                    })?,
+                    None => DEFAULT_TRIAGE_DAYS,
+                },
+            },
+            "sprint_review" => Self::SprintReview {
+                board: optional("board"),
+                project: optional("project"),
+                sprint: optional("sprint"),
+            },
+            "break_epic" => Self::BreakEpic {
+                epic: required("epic")?,
+            },
+            _ => {
+                return Err(JiraError::validation(format!(
+                    "Unknown prompt '{}'. Available prompts: {}",
+                    name,
+                    Self::NAMES.join(", ")
+                )));
+            }
+        };
+        Ok((prompt, optional("workspace")))
+    }
+
+    /// The tools the prompt fetches its data with.
+    pub fn tools(name: &str) -> &'static [&'static str] {
+        match name {
+            "groom_backlog" => &["board_get_backlog"],
+            "triage_bugs" => &["search_issues"],
+            "sprint_review" => &["board_get_sprints", "search_issues"],
+            "break_epic" => &["issue_get", "search_issues"],
+            _ => &[],
+        }
+    }
+
+    /// The task given to the assistant, ahead of the embedded data.
+    pub fn instructions(&self) -> String {
+        match self {
+            Self::GroomBacklog { board, project } => format!(
+                "Groom the backlog of {}, included below in rank order.\n\n\
+                 For each issue, flag what keeps it from being ready: an unclear summary, \
+                 missing acceptance criteria, no estimate, a likely duplicate, or no activity \
+                 for a long time. Then suggest a new order for the top of the backlog, \
+                 stories that should be split, and issues that could be closed. \
+                 Do not change anything in Jira until I confirm; use `issue_get` when you \
+                 need the full description of an issue.",
+                describe_board(board, project)
+            ),
+            Self::TriageBugs { project, days } => format!(
+                "Triage the bugs created in project {} during the last {} days that are not \
+                 done yet, included below, newest first.\n\n\
+                 For each bug, propose a priority, say whether it has enough information \
+                 to reproduce it (and what to ask the reporter if not), point out likely \
+                 duplicates among them, and suggest who or which component should own it. \
+                 Finish with the bugs to fix first. Do not change anything in Jira until \
+                 I confirm.",
+                project, days
+            ),
+            Self::SprintReview { sprint, .. } => format!(
+                "Write a sprint review for {}, whose details and issues are included below.\n\n\
+                 Summarize what was completed against the sprint goal, what was not and why \
+                 it might have slipped, notable bugs, and the work carried over. Keep it \
+                 short enough to share with stakeholders, grouped by theme rather than by \
+                 issue, and end with points to discuss in the retrospective.",
+                sprint
+                    .as_ref()
+                    .map(|s| format!("sprint '{}'", s))
+                    .unwrap_or_else(|| "the active sprint (or the last closed one)".to_string())
+            ),
+            Self::BreakEpic { epic } => format!(
+                "Break epic {} into user stories. The epic and the issues already under it \
+                 are included below.\n\n\
+                 Propose stories that together deliver the epic, each with a summary, a \
+                 short description in the form \"As a ..., I want ..., so that ...\", \
+                 acceptance criteria and a rough size. Do not repeat work the existing \
+                 child issues cover, and call out open questions. Do not create anything \
+                 until I confirm; then use `issue_create` with `parentKey` set to {}.",
+                epic, epic
+            ),
+        }
+    }
+}
+
+/// The prompts advertised by `prompts/list`.
+pub fn list() -> Vec<Prompt> {
+    let argument = |name: &str, description: &str, required: bool| PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: Some(description.to_string()),
+        required: Some(required),
+    };
+    let board = argument(
+        "board",
+        "Board name. Either board or project is required.",
+        false,
+    );
+    let project = argument("project", "Project key whose board to use.", false);
+    let workspace = argument(
+        "workspace",
+        "Workspace (profile) name. Defaults to the primary workspace.",
+        false,
+    );
+
+    vec![
+        Prompt::new(
+            "groom_backlog",
+            Some(
+                "Groom the backlog of a board: find issues that are not ready and suggest order, splits and closures.",
+            ),
+            Some(vec![board.clone(), project.clone(), workspace.clone()]),
+        ),
+        Prompt::new(
+            "triage_bugs",
+            Some("Triage the open bugs recently created in a project."),
+            Some(vec![
+                argument("project", "Project key.", true),
+                argument(
+                    "days",
+                    &format!(
+                        "How many days back to look (default {}).",
+                        DEFAULT_TRIAGE_DAYS
+                    ),
+                    false,
+                ),
+                workspace.clone(),
+            ]),
+        ),
+        Prompt::new(
+            "sprint_review",
+            Some("Write a sprint review from a sprint's issues."),
+            Some(vec![
+                board,
+                project,
+                argument(
+                    "sprint",
+                    "Sprint id or name. Defaults to the active sprint, or the last closed one.",
+                    false,
+                ),
+                workspace.clone(),
+            ]),
+        ),
+        Prompt::new(
+            "break_epic",
+            Some("Break an epic into user stories."),
+            Some(vec![argument("epic", "Epic issue key.", true), workspace]),
+        ),
+    ]
+}
+
+/// A user message presenting `data` as a JSON block under `title`.
+pub fn data_message(title: &str, data: &impl Serialize) -> Result<PromptMessage, JiraError> {
+    Ok(PromptMessage::new_text(
+        PromptMessageRole::User,
+        format!(
+            "{}:\n\n```json\n{}\n```",
+            title,
+            serde_json::to_string_pretty(data)?
+        ),
+    ))
+}
+
+fn describe_board(board: &Option<String>, project: &Option<String>) -> String {
+    match (board, project) {
+        (Some(board), _) => format!("board '{}'", board),
+        (None, Some(project)) => format!("the board of project {}", project),
+        (None, None) => "the board".to_string(),
+    }
+}
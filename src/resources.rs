@@ -0,0 +1,224 @@
+//! MCP resources: Jira content a client can attach as context by URI,
+//! without a tool call.
+//!
+//! | URI                          | Content                              |
+//! |------------------------------|--------------------------------------|
+//! | `jira://issue/{key}`         | Issue with the `detailed` fields     |
+//! | `jira://sprint/{id}`         | Sprint and its issues                |
+//! | `jira://board/{id}/backlog`  | Backlog issues of a board            |
+//! | `jira://filter/{id}`         | Saved filter and the issues it finds |
+//!
+//! Any URI accepts `?workspace=NAME` to read from another workspace.
+
+use crate::domains;
+use crate::domains::enums::FieldPreset;
+use crate::error::JiraError;
+use crate::site::{Method, Site};
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+
+pub const MIME_TYPE: &str = "application/json";
+
+/// Fields read for the issues listed in a sprint, backlog or filter.
+const LIST_FIELDS: &str = "summary,status,assignee,issuetype,priority";
+
+/// Most issues embedded in a sprint, backlog or filter resource.
+const MAX_ISSUES: i32 = 50;
+
+/// Recently viewed issues offered by `resources/list`.
+const RECENT_ISSUES: i32 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JiraUri {
+    Issue(String),
+    Sprint(u64),
+    BoardBacklog(u64),
+    Filter(u64),
+}
+
+impl JiraUri {
+    /// Parses a `jira://` URI into its target and optional workspace.
+    pub fn parse(uri: &str) -> Option<(Self, Option<String>)> {
+        let rest = uri.strip_prefix("jira://")?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let workspace = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("workspace="))
+            .filter(|w| !w.is_empty())
+            .map(|w| {
+                urlencoding::decode(w)
+                    .map(|w| w.into_owned())
+                    .unwrap_or_else(|_| w.to_string())
+            });
+
+        let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+        let target = match segments.as_slice() {
+            ["issue", key] if !key.is_empty() => Self::Issue(key.to_string()),
+            ["sprint", id] => Self::Sprint(id.parse().ok()?),
+            ["board", id, "backlog"] => Self::BoardBacklog(id.parse().ok()?),
+            ["filter", id] => Self::Filter(id.parse().ok()?),
+            _ => return None,
+        };
+        Some((target, workspace))
+    }
+
+    /// Name of the template the URI belongs to, as listed by [`templates`].
+    pub fn template(&self) -> &'static str {
+        match self {
+            Self::Issue(_) => "issue",
+            Self::Sprint(_) => "sprint",
+            Self::BoardBacklog(_) => "board-backlog",
+            Self::Filter(_) => "filter",
+        }
+    }
+
+    /// The tool that reads the same data as the resources of `template`.
+    /// They are only served while the tool policy enables it.
+    pub fn tool(template: &str) -> &'static str {
+        match template {
+            "issue" => "issue_get",
+            "sprint" => "board_get_sprints",
+            "board-backlog" => "board_get_backlog",
+            _ => "search_issues",
+        }
+    }
+}
+
+/// The URI templates advertised by `resources/templates/list`.
+pub fn templates() -> Vec<ResourceTemplate> {
+    [
+        (
+            "jira://issue/{key}",
+            "issue",
+            "A Jira issue: summary, status, assignee, priority, dates, description, labels, components and links.",
+        ),
+        (
+            "jira://sprint/{id}",
+            "sprint",
+            "A sprint with its dates, goal and issues.",
+        ),
+        (
+            "jira://board/{id}/backlog",
+            "board-backlog",
+            "The backlog issues of an agile board, in rank order.",
+        ),
+        (
+            "jira://filter/{id}",
+            "filter",
+            "A saved filter with its JQL and the issues it currently matches.",
+        ),
+    ]
+    .into_iter()
+    .map(|(uri_template, name, description)| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(MIME_TYPE.to_string()),
+            icons: None,
+        }
+        .no_annotation()
+    })
+    .collect()
+}
+
+/// The issues the user viewed most recently, as `jira://issue/{key}` resources.
+pub async fn recent(site: &Site) -> Result<Vec<Resource>, JiraError> {
+    let page = site
+        .search(
+            "issue in issueHistory() ORDER BY lastViewed DESC",
+            Some(vec!["summary".to_string()]),
+            Some(RECENT_ISSUES),
+            None,
+        )
+        .await?;
+    Ok(page
+        .issues
+        .into_iter()
+        .map(|issue| {
+            let mut resource =
+                RawResource::new(format!("jira://issue/{}", issue.key), issue.key.clone());
+            resource.title = issue
+                .fields
+                .get("summary")
+                .and_then(|s| s.as_str())
+                .map(str::to_string);
+            resource.mime_type = Some(MIME_TYPE.to_string());
+            resource.no_annotation()
+        })
+        .collect())
+}
+
+/// Downloads the content of `target` as JSON.
+pub async fn read(site: &Site, target: &JiraUri) -> Result<serde_json::Value, JiraError> {
+    let list_query = vec![
+        ("fields", LIST_FIELDS.to_string()),
+        ("maxResults", MAX_ISSUES.to_string()),
+    ];
+    let value = match target {
+        JiraUri::Issue(key) => {
+            let url = site.api(&format!("/issue/{}", key));
+            let query = vec![("fields", FieldPreset::Detailed.to_field_list())];
+            let issue = site
+                .send_request::<domains::issue::Issue, ()>(&url, Method::Get, Some(&query), None)
+                .await?;
+            serde_json::to_value(issue)?
+        }
+        JiraUri::Sprint(id) => {
+            let url = format!("/rest/agile/1.0/sprint/{}", id);
+            let sprint = site
+                .send_request::<domains::sprint::Sprint, ()>(&url, Method::Get, None, None)
+                .await?;
+            let issues = site
+                .send_request::<domains::jql::IssuePage, ()>(
+                    &format!("{}/issue", url),
+                    Method::Get,
+                    Some(&list_query),
+                    None,
+                )
+                .await?;
+            serde_json::json!({
+                "sprint": sprint,
+                "issues": issues.issues,
+                "total": issues.total,
+            })
+        }
+        JiraUri::BoardBacklog(id) => {
+            let url = format!("/rest/agile/1.0/board/{}/backlog", id);
+            let issues = site
+                .send_request::<domains::jql::IssuePage, ()>(
+                    &url,
+                    Method::Get,
+                    Some(&list_query),
+                    None,
+                )
+                .await?;
+            serde_json::json!({
+                "boardId": id,
+                "issues": issues.issues,
+                "total": issues.total,
+            })
+        }
+        JiraUri::Filter(id) => {
+            let url = site.api(&format!("/filter/{}", id));
+            let filter = site
+                .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None)
+                .await?;
+            let jql = filter["jql"].as_str().unwrap_or_default();
+            let fields = LIST_FIELDS.split(',').map(str::to_string).collect();
+            let issues = site
+                .search(jql, Some(fields), Some(MAX_ISSUES), None)
+                .await?;
+            serde_json::json!({
+                "filter": {
+                    "id": filter["id"],
+                    "name": filter["name"],
+                    "jql": jql,
+                },
+                "issues": issues.issues,
+                "isLast": issues.is_last,
+            })
+        }
+    };
+    Ok(value)
+}
@@ -0,0 +1,106 @@
+use rand::Rng;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Retry behaviour of `Jira::send_request` when Jira throttles or is unavailable.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt. `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay of the first retry; doubled on every following attempt.
+    pub base_delay: Duration,
+    /// Upper bound for a single wait, computed or requested by Jira.
+    pub max_delay: Duration,
+    /// Also retry POST requests, which Jira does not guarantee to be idempotent.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Reads `JIRA_MAX_RETRIES`, `JIRA_RETRY_BASE_DELAY_MS`, `JIRA_RETRY_MAX_DELAY_MS`
+    /// and `JIRA_RETRY_NON_IDEMPOTENT`, falling back to the defaults.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+
+        Self {
+            max_retries: env_u64("JIRA_MAX_RETRIES")
+                .map(|v| v as u32)
+                .unwrap_or(default.max_retries),
+            base_delay: env_u64("JIRA_RETRY_BASE_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.base_delay),
+            max_delay: env_u64("JIRA_RETRY_MAX_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.max_delay),
+            retry_non_idempotent: std::env::var("JIRA_RETRY_NON_IDEMPOTENT")
+                .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(default.retry_non_idempotent),
+        }
+    }
+
+    /// Status codes worth retrying: rate limiting and transient gateway failures.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Exponential backoff for the given retry (0-based), jittered between
+    /// half and the full delay so concurrent callers spread out.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let millis = exp.as_millis() as u64;
+        if millis == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(rand::rng().random_range(millis / 2..=millis))
+    }
+
+    /// Wait requested by Jira through `Retry-After` (seconds or HTTP date)
+    /// or `X-RateLimit-Reset` (ISO 8601 timestamp).
+    pub fn requested_delay(headers: &HeaderMap) -> Option<Duration> {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let until = |date: chrono::DateTime<chrono::FixedOffset>| {
+            (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                .to_std()
+                .ok()
+                .or(Some(Duration::ZERO))
+        };
+
+        if let Some(value) = header("retry-after") {
+            if let Ok(secs) = value.trim().parse::<u64>() {
+                return Some(Duration::from_secs(secs));
+            }
+            if let Ok(date) = chrono::DateTime::parse_from_rfc2822(value.trim()) {
+                return until(date);
+            }
+        }
+
+        header("x-ratelimit-reset")
+            .and_then(|v| chrono::DateTime::parse_from_rfc3339(v.trim()).ok())
+            .and_then(until)
+    }
+}
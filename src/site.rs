@@ -0,0 +1,777 @@
+use crate::auth::Auth;
+use crate::cache::MetadataCache;
+use crate::domains;
+use crate::error::JiraError;
+use crate::retry::RetryPolicy;
+use reqwest::header::{CONTENT_TYPE, RANGE};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// Most items `agile_list` collects across pages.
+const MAX_AGILE_ITEMS: i32 = 1000;
+
+/// Page size `agile_list` requests when collecting every page; the most
+/// the Agile API returns at once.
+const AGILE_PAGE_SIZE: usize = 50;
+
+/// Flavour of the Jira instance, which decides the REST API version,
+/// the rich text format and the search pagination style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Deployment {
+    /// Jira Cloud: REST API v3, ADF bodies, token based search pagination.
+    Cloud,
+    /// Jira Data Center / Server: REST API v2, wiki markup, `startAt` pagination.
+    DataCenter,
+}
+
+impl Deployment {
+    /// Parses `cloud`, `datacenter`/`dc` or `server`. `auto` and unknown values yield `None`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "cloud" => Some(Deployment::Cloud),
+            "datacenter" | "data-center" | "data_center" | "dc" | "server" => {
+                Some(Deployment::DataCenter)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn api_version(self) -> u8 {
+        match self {
+            Deployment::Cloud => 3,
+            Deployment::DataCenter => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        }
+    }
+
+    /// GET, PUT and DELETE can be replayed safely; POST may create duplicates.
+    pub(crate) fn is_idempotent(self) -> bool {
+        !matches!(self, Method::Post)
+    }
+}
+
+/// Connection to a single Jira site: where it lives, how to authenticate and
+/// which API flavour it speaks. Tools pick one per call by workspace name.
+#[derive(Debug)]
+pub struct Site {
+    client: reqwest::Client,
+    base_url: String,
+    auth: Auth,
+    deployment: Deployment,
+    retry: RetryPolicy,
+    cache: MetadataCache,
+}
+
+impl Site {
+    /// `base_url` is the site root every REST path is appended to, e.g.
+    /// `https://mycompany.atlassian.net` or `https://proxy.corp/jira`.
+    pub fn new(base_url: &str, auth: Auth) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth,
+            deployment: Deployment::Cloud,
+            retry: RetryPolicy::default(),
+            cache: MetadataCache::new(MetadataCache::DEFAULT_TTL),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Time-to-live of the field, createmeta and editmeta caches; zero disables them.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache = MetadataCache::new(ttl);
+        self
+    }
+
+    pub fn with_deployment(mut self, deployment: Deployment) -> Self {
+        self.deployment = deployment;
+        self
+    }
+
+    /// Asks `/rest/api/2/serverInfo` (available on every flavour) which
+    /// deployment this is, defaulting to Cloud when the answer is unclear.
+    pub async fn detect_deployment(&self) -> Result<Deployment, JiraError> {
+        let info: serde_json::Value = self
+            .send_request::<_, ()>("/rest/api/2/serverInfo", Method::Get, None, None::<&()>)
+            .await?;
+        let deployment = match info.get("deploymentType").and_then(|d| d.as_str()) {
+            Some(kind) if !kind.eq_ignore_ascii_case("cloud") => Deployment::DataCenter,
+            _ => Deployment::Cloud,
+        };
+        Ok(deployment)
+    }
+
+    /// Base URL of a Jira Cloud site from its subdomain.
+    pub fn cloud_url(workspace: &str) -> String {
+        format!("https://{}.atlassian.net", workspace)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Platform REST path for the configured deployment, e.g. `/issue` ->
+    /// `/rest/api/3/issue` on Cloud and `/rest/api/2/issue` on Data Center.
+    pub(crate) fn api(&self, path: &str) -> String {
+        format!("/rest/api/{}{}", self.deployment.api_version(), path)
+    }
+
+    /// Rich text body: Markdown converted to ADF on Cloud, with mentions
+    /// resolved to users; wiki markup (plain string) on Data Center.
+    pub(crate) async fn rich_text(&self, text: &str) -> Result<serde_json::Value, JiraError> {
+        match self.deployment {
+            Deployment::Cloud => {
+                let mut doc = domains::helpers::markdown_to_adf(text).0;
+                crate::mentions::resolve(self, &mut doc).await?;
+                Ok(doc)
+            }
+            Deployment::DataCenter => Ok(serde_json::json!(text)),
+        }
+    }
+
+    pub fn deployment(&self) -> Deployment {
+        self.deployment
+    }
+
+    pub(crate) async fn send_request<T, B>(
+        &self,
+        url: &str,
+        method: Method,
+        query_params: Option<&Vec<(&str, String)>>,
+        body: Option<&B>,
+    ) -> Result<T, JiraError>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        let resp = self
+            .send(url, method, query_params, |req_builder| {
+                let req_builder = req_builder.header(CONTENT_TYPE, "application/json");
+                Ok(match body {
+                    Some(b) => req_builder.json(b),
+                    None => req_builder,
+                })
+            })
+            .await?;
+
+        let res_text = resp.text().await?;
+        if res_text.is_empty() || res_text == "null" {
+            return serde_json::from_str("null").map_err(JiraError::from);
+        }
+
+        serde_json::from_str::<T>(&res_text).map_err(JiraError::from)
+    }
+
+    /// Uploads `data` as the multipart `file` field Jira expects for
+    /// attachments. The `X-Atlassian-Token` header skips Jira's XSRF check,
+    /// which otherwise rejects multipart requests.
+    pub(crate) async fn upload<T>(
+        &self,
+        url: &str,
+        file_name: &str,
+        data: Vec<u8>,
+    ) -> Result<T, JiraError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mime = mime_guess::from_path(file_name).first_or_octet_stream();
+        let resp = self
+            .send(url, Method::Post, None, |req_builder| {
+                let part = reqwest::multipart::Part::bytes(data.clone())
+                    .file_name(file_name.to_string())
+                    .mime_str(mime.as_ref())
+                    .map_err(|e| {
+                        JiraError::validation(format!(
+                            "Cannot attach '{}' as {}: {}",
+                            file_name, mime, e
+                        ))
+                    })?;
+                Ok(req_builder
+                    .header("X-Atlassian-Token", "no-check")
+                    .multipart(reqwest::multipart::Form::new().part("file", part)))
+            })
+            .await?;
+        serde_json::from_slice(&resp.bytes().await?).map_err(JiraError::from)
+    }
+
+    /// Fetches the raw content at `url`, with its content type. With a
+    /// `limit`, only that many bytes are asked for and read (servers may
+    /// still send everything), plus one to tell whether there was more.
+    pub(crate) async fn download(
+        &self,
+        url: &str,
+        limit: Option<usize>,
+    ) -> Result<(Vec<u8>, Option<String>), JiraError> {
+        let mut resp = self
+            .send(url, Method::Get, None, |req_builder| {
+                Ok(match limit {
+                    Some(limit) => req_builder.header(RANGE, format!("bytes=0-{}", limit)),
+                    None => req_builder,
+                })
+            })
+            .await?;
+        let content_type = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let Some(limit) = limit else {
+            return Ok((resp.bytes().await?.to_vec(), content_type));
+        };
+        // Stop reading once past the limit, in case the Range was ignored
+        let mut data = Vec::new();
+        while data.len() <= limit {
+            match resp.chunk().await? {
+                Some(chunk) => data.extend_from_slice(&chunk),
+                None => break,
+            }
+        }
+        data.truncate(limit + 1);
+        Ok((data, content_type))
+    }
+
+    /// Sends a request, retrying and re-authenticating as configured, and
+    /// returns the response when it succeeded. `prepare` adds the headers
+    /// and body, or fails before anything is sent; it runs again for every
+    /// attempt.
+    async fn send(
+        &self,
+        url: &str,
+        method: Method,
+        query_params: Option<&Vec<(&str, String)>>,
+        prepare: impl Fn(reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder, JiraError>,
+    ) -> Result<reqwest::Response, JiraError> {
+        let full_url = if url.starts_with("http") {
+            // Credentials are only ever sent to the configured site, never
+            // to a URL taken from a response
+            let origin = |u: &str| reqwest::Url::parse(u).ok().map(|u| u.origin());
+            if origin(url).is_none() || origin(url) != origin(self.base_url()) {
+                return Err(JiraError::validation(format!(
+                    "Refusing to send a request to {}: it is not on the site {}",
+                    url,
+                    self.base_url()
+                )));
+            }
+            url.to_string()
+        } else {
+            format!("{}{}", self.base_url(), url)
+        };
+
+        let span = tracing::debug_span!(
+            "jira_request",
+            method = method.as_str(),
+            url = %full_url,
+            attempts = tracing::field::Empty,
+            status = tracing::field::Empty,
+        );
+        let can_retry = method.is_idempotent() || self.retry.retry_non_idempotent;
+        let mut attempt: u32 = 0;
+        let mut reauthenticated = false;
+
+        let resp = loop {
+            attempt += 1;
+            span.record("attempts", attempt);
+
+            let mut req_builder = match method {
+                Method::Get => self.client.get(&full_url),
+                Method::Post => self.client.post(&full_url),
+                Method::Put => self.client.put(&full_url),
+                Method::Delete => self.client.delete(&full_url),
+            };
+
+            req_builder = prepare(self.auth.apply(&self.client, req_builder).await?)?;
+
+            if let Some(params) = query_params {
+                req_builder = req_builder.query(params);
+            }
+
+            let retries_left = can_retry && attempt <= self.retry.max_retries;
+            let delay = match req_builder.send().instrument(span.clone()).await {
+                // Expired or revoked OAuth access token: refresh once and replay
+                Ok(resp)
+                    if resp.status() == reqwest::StatusCode::UNAUTHORIZED
+                        && !reauthenticated
+                        && self.auth.refresh(&self.client).await? =>
+                {
+                    reauthenticated = true;
+                    continue;
+                }
+                Ok(resp) if retries_left && RetryPolicy::is_retryable_status(resp.status()) => {
+                    match RetryPolicy::requested_delay(resp.headers()) {
+                        Some(requested) if requested > self.retry.max_delay => break resp,
+                        Some(requested) => requested,
+                        None => self.retry.backoff(attempt - 1),
+                    }
+                }
+                Ok(resp) => break resp,
+                Err(e) if retries_left && (e.is_connect() || e.is_timeout()) => {
+                    self.retry.backoff(attempt - 1)
+                }
+                Err(e) => {
+                    crate::audit::record(method, &full_url, None, Some(e.to_string())).await;
+                    return Err(e.into());
+                }
+            };
+
+            span.in_scope(|| {
+                tracing::warn!(
+                    "Jira request attempt {} failed, retrying in {:?}",
+                    attempt,
+                    delay
+                )
+            });
+            tokio::time::sleep(delay).await;
+        };
+
+        span.record("status", resp.status().as_u16());
+        crate::audit::record(method, &full_url, Some(resp.status().as_u16()), None).await;
+
+        if !resp.status().is_success() {
+            let status = resp.status().as_u16();
+            let text = resp.text().await?;
+            return Err(JiraError::from_response(status, &text));
+        }
+        Ok(resp)
+    }
+
+    pub(crate) fn cache(&self) -> &MetadataCache {
+        &self.cache
+    }
+
+    /// Every field definition of the site, served from the cache when fresh.
+    pub(crate) async fn fields(&self) -> Result<Arc<Vec<serde_json::Value>>, JiraError> {
+        if let Some(fields) = self.cache.fields.get("") {
+            return Ok(fields);
+        }
+        let url = self.api("/field");
+        let fields = Arc::new(
+            self.send_request::<Vec<serde_json::Value>, ()>(&url, Method::Get, None, None::<&()>)
+                .await?,
+        );
+        self.cache.fields.insert("", fields.clone());
+        Ok(fields)
+    }
+
+    pub(crate) async fn find_field_id(&self, name: &str) -> Result<Option<String>, JiraError> {
+        let fields = self.fields().await?;
+        for field in fields.iter() {
+            if let Some(field_name) = field.get("name").and_then(|n| n.as_str())
+                && field_name.eq_ignore_ascii_case(name)
+            {
+                return Ok(field
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .map(|s| s.to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Edit metadata of an issue, served from the cache when fresh.
+    pub(crate) async fn editmeta(
+        &self,
+        issue_key: &str,
+    ) -> Result<Arc<serde_json::Value>, JiraError> {
+        if let Some(meta) = self.cache.editmeta.get(issue_key) {
+            return Ok(meta);
+        }
+        let url = self.api(&format!("/issue/{}/editmeta", issue_key));
+        let meta = Arc::new(
+            self.send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
+                .await?,
+        );
+        self.cache.editmeta.insert(issue_key, meta.clone());
+        Ok(meta)
+    }
+
+    pub(crate) async fn get_editable_field_id(
+        &self,
+        issue_key: &str,
+        possible_names: &[&str],
+    ) -> Result<Option<String>, JiraError> {
+        let meta = self.editmeta(issue_key).await?;
+
+        let Some(fields) = meta.get("fields").and_then(|f| f.as_object()) else {
+            return Ok(None);
+        };
+
+        for (id, field_info) in fields {
+            if let Some(name) = field_info.get("name").and_then(|n| n.as_str()) {
+                for target in possible_names {
+                    if name.eq_ignore_ascii_case(target) {
+                        return Ok(Some(id.clone()));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Issue types available for creation in a project, from createmeta.
+    pub(crate) async fn project_issue_types(
+        &self,
+        project_key: &str,
+    ) -> Result<Arc<Vec<serde_json::Value>>, JiraError> {
+        if let Some(types) = self.cache.createmeta.get(project_key) {
+            return Ok(types);
+        }
+        let types = match self.deployment {
+            Deployment::Cloud => {
+                let url = self.api(&format!(
+                    "/issue/createmeta?projectKeys={}&expand=projects.issuetypes",
+                    project_key
+                ));
+                let meta: serde_json::Value = self
+                    .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
+                    .await?;
+
+                let project = meta
+                    .get("projects")
+                    .and_then(|p| p.as_array())
+                    .and_then(|projects| {
+                        projects.iter().find(|p| {
+                            p.get("key").and_then(|k| k.as_str()).unwrap_or("") == project_key
+                        })
+                    })
+                    .ok_or_else(|| JiraError::not_found("Project", project_key))?;
+                project.get("issuetypes").cloned()
+            }
+            Deployment::DataCenter => {
+                // Data Center 9 removed the `projectKeys` variant of createmeta
+                let url = self.api(&format!("/issue/createmeta/{}/issuetypes", project_key));
+                let meta: serde_json::Value = self
+                    .send_request::<serde_json::Value, ()>(&url, Method::Get, None, None::<&()>)
+                    .await?;
+                meta.get("values").cloned()
+            }
+        };
+
+        let types = Arc::new(
+            types
+                .and_then(|t| t.as_array().cloned())
+                .unwrap_or_default(),
+        );
+        self.cache.createmeta.insert(project_key, types.clone());
+        Ok(types)
+    }
+
+    pub(crate) async fn resolve_issue_type_id(
+        &self,
+        project_key: &str,
+        issue_type: &str,
+    ) -> Result<(String, bool), JiraError> {
+        let types = self.project_issue_types(project_key).await?;
+        let target = issue_type;
+
+        for t in types.iter() {
+            let name = t.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            let untranslated = t
+                .get("untranslatedName")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let id = t.get("id").and_then(|v| v.as_str()).unwrap_or("");
+
+            // Match by ID, name, or untranslated name (homologs)
+            if id == target
+                || name.eq_ignore_ascii_case(target)
+                || untranslated.eq_ignore_ascii_case(target)
+            {
+                let is_subtask = t.get("subtask").and_then(|v| v.as_bool()).unwrap_or(false);
+                return Ok((id.to_string(), is_subtask));
+            }
+        }
+
+        Err(JiraError::not_found_with_hint(
+            "Issue type",
+            issue_type,
+            format!(
+                "No matching issue type in project {}. Use 'list_issue_types' to see available types.",
+                project_key
+            ),
+        ))
+    }
+
+    pub(crate) async fn find_transition_id(
+        &self,
+        issue_key: &str,
+        target_status: domains::enums::Status,
+    ) -> Result<String, JiraError> {
+        let url = self.api(&format!("/issue/{}/transitions", issue_key));
+        let resp: domains::issue::TransitionResponse = self
+            .send_request::<_, ()>(&url, Method::Get, None, None::<&()>)
+            .await?;
+
+        let target_name = target_status.to_string(); // e.g. "In Progress"
+
+        for transition in &resp.transitions {
+            if transition.name.eq_ignore_ascii_case(&target_name) {
+                return Ok(transition.id.clone());
+            }
+            if transition.to.name.eq_ignore_ascii_case(&target_name) {
+                return Ok(transition.id.clone());
+            }
+        }
+
+        let target_category = match target_status {
+            domains::enums::Status::ToDo => "new",
+            domains::enums::Status::InProgress | domains::enums::Status::InReview => {
+                "indeterminate"
+            }
+            domains::enums::Status::Done | domains::enums::Status::Cancelled => "done",
+            domains::enums::Status::Blocked => "indeterminate",
+        };
+
+        for transition in &resp.transitions {
+            if let Some(cat) = &transition.to.status_category
+                && cat.key.eq_ignore_ascii_case(target_category)
+            {
+                return Ok(transition.id.clone());
+            }
+        }
+
+        Err(JiraError::not_found_with_hint(
+            "Transition",
+            target_name,
+            format!(
+                "No available transition from the current status of issue {}",
+                issue_key
+            ),
+        ))
+    }
+
+    /// Transition leading back to a given status, used to reverse a previous
+    /// transition. Matches the status id when known, otherwise its name.
+    pub(crate) async fn find_transition_to(
+        &self,
+        issue_key: &str,
+        status: &str,
+        status_id: Option<&str>,
+    ) -> Result<String, JiraError> {
+        let url = self.api(&format!("/issue/{}/transitions", issue_key));
+        let resp: domains::issue::TransitionResponse = self
+            .send_request::<_, ()>(&url, Method::Get, None, None::<&()>)
+            .await?;
+
+        resp.transitions
+            .iter()
+            .find(|t| match (status_id, t.to.id.as_deref()) {
+                (Some(wanted), Some(id)) => wanted == id,
+                _ => t.to.name.eq_ignore_ascii_case(status),
+            })
+            .map(|t| t.id.clone())
+            .ok_or_else(|| {
+                JiraError::not_found_with_hint(
+                    "Transition",
+                    status,
+                    format!(
+                        "The workflow has no transition from the current status of issue {} back to '{}'",
+                        issue_key, status
+                    ),
+                )
+            })
+    }
+
+    /// The user the credentials belong to.
+    pub async fn myself(&self) -> Result<domains::user::User, JiraError> {
+        self.send_request::<_, ()>(&self.api("/myself"), Method::Get, None, None::<&()>)
+            .await
+    }
+
+    pub(crate) async fn resolve_assignee(&self, assignee: &str) -> Result<String, JiraError> {
+        if assignee.eq_ignore_ascii_case("me") {
+            let resp = self.myself().await?;
+            return match self.deployment {
+                Deployment::Cloud => Ok(resp.account_id),
+                Deployment::DataCenter => resp
+                    .name
+                    .ok_or_else(|| JiraError::not_found("User", assignee)),
+            };
+        }
+        if assignee.eq_ignore_ascii_case("unassigned") {
+            return Ok("".to_string());
+        }
+        Ok(assignee.to_string())
+    }
+
+    /// Runs a JQL search. Cloud pages with `nextPageToken` on `/search/jql`;
+    /// Data Center only offers the classic `/search` endpoint paged with
+    /// `startAt`, so there the page token is the `startAt` of the next page.
+    pub(crate) async fn search(
+        &self,
+        jql: &str,
+        fields: Option<Vec<String>>,
+        limit: Option<i32>,
+        page_token: Option<&str>,
+    ) -> Result<domains::jql::SearchPage, JiraError> {
+        let url = match self.deployment {
+            Deployment::Cloud => self.api("/search/jql"),
+            Deployment::DataCenter => self.api("/search"),
+        };
+
+        let mut body = serde_json::Map::new();
+        body.insert("jql".to_string(), serde_json::json!(jql));
+        let mut start_at = 0;
+        match self.deployment {
+            Deployment::Cloud => {
+                if let Some(token) = page_token {
+                    body.insert("nextPageToken".to_string(), serde_json::json!(token));
+                }
+            }
+            Deployment::DataCenter => {
+                if let Some(token) = page_token {
+                    start_at = token.parse().map_err(|_| {
+                        JiraError::validation(format!("Invalid page token '{}'", token))
+                    })?;
+                }
+                body.insert("startAt".to_string(), serde_json::json!(start_at));
+            }
+        }
+        if let Some(limit) = limit {
+            body.insert("maxResults".to_string(), serde_json::json!(limit));
+        }
+        body.insert("fieldsByKeys".to_string(), serde_json::json!(true));
+        if let Some(fields) = fields {
+            body.insert("fields".to_string(), serde_json::json!(fields));
+        }
+
+        let page: domains::jql::IssuePage = self
+            .send_request(&url, Method::Post, None, Some(&body))
+            .await?;
+        let next_page_token = match self.deployment {
+            Deployment::Cloud => page.next_page_token.filter(|_| page.is_last != Some(true)),
+            Deployment::DataCenter => {
+                let next = start_at + page.issues.len() as i64;
+                (!page.issues.is_empty() && page.total.is_some_and(|total| next < total))
+                    .then(|| next.to_string())
+            }
+        };
+        Ok(domains::jql::SearchPage {
+            issues: page.issues,
+            is_last: next_page_token.is_none(),
+            next_page_token,
+        })
+    }
+
+    /// Lists `url` from the Agile API: one page from `start_at`, or with
+    /// `all_pages` every page up to `limit` items.
+    pub(crate) async fn agile_list<P: domains::agile::AgilePage>(
+        &self,
+        url: &str,
+        query: Vec<(&str, String)>,
+        start_at: Option<i64>,
+        limit: Option<i32>,
+        all_pages: bool,
+    ) -> Result<P, JiraError> {
+        let page = |start: i64, size: Option<i32>| {
+            let mut query = query.clone();
+            if start > 0 {
+                query.push(("startAt", start.to_string()));
+            }
+            if let Some(size) = size {
+                query.push(("maxResults", size.to_string()));
+            }
+            async move {
+                self.send_request::<P, ()>(url, Method::Get, Some(&query), None::<&()>)
+                    .await
+            }
+        };
+
+        let start = start_at.unwrap_or(0);
+        if !all_pages {
+            return page(start, limit).await;
+        }
+
+        let cap = limit.unwrap_or(MAX_AGILE_ITEMS).clamp(1, MAX_AGILE_ITEMS) as usize;
+        let mut list = page(start, Some(cap.min(AGILE_PAGE_SIZE) as i32)).await?;
+        while list.len() < cap && !list.is_last() {
+            let size = (cap - list.len()).min(AGILE_PAGE_SIZE) as i32;
+            let next = page(start + list.len() as i64, Some(size)).await?;
+            if next.is_empty() {
+                break;
+            }
+            list.append(next);
+        }
+        Ok(list)
+    }
+
+    pub(crate) async fn find_board_id(
+        &self,
+        board_name: Option<String>,
+        project_key: Option<String>,
+    ) -> Result<i64, JiraError> {
+        let (query, label) = match (board_name, project_key) {
+            (Some(name), _) => (vec![("name", name.clone())], name),
+            (None, Some(project)) => (vec![("projectKeyOrId", project.clone())], project),
+            (None, None) => {
+                return Err(JiraError::validation(
+                    "Either boardName or projectKey is required",
+                ));
+            }
+        };
+
+        let url = "/rest/agile/1.0/board";
+        let resp: serde_json::Value = self
+            .send_request::<_, ()>(url, Method::Get, Some(&query), None::<&()>)
+            .await?;
+        resp.get("values")
+            .and_then(|v| v.as_array())
+            .and_then(|a| a.first())
+            .and_then(|b| b.get("id"))
+            .and_then(|id| id.as_i64())
+            .ok_or_else(|| JiraError::not_found("Board", label))
+    }
+
+    /// Data Center has no bulk archive endpoint: archive or restore
+    /// (`action`) issue by issue and report the outcome of each one.
+    pub(crate) async fn archive_each(
+        &self,
+        issue_keys: &[String],
+        action: &str,
+    ) -> Result<domains::issue::ArchiveResult, JiraError> {
+        let mut updated = Vec::new();
+        let mut errors = serde_json::Map::new();
+        for key in issue_keys {
+            let url = self.api(&format!("/issue/{}/{}", key, action));
+            match self
+                .send_request::<serde_json::Value, ()>(&url, Method::Put, None, None::<&()>)
+                .await
+            {
+                Ok(_) => updated.push(key.clone()),
+                Err(e) => {
+                    errors.insert(key.clone(), serde_json::json!(e.to_string()));
+                }
+            }
+        }
+        Ok(domains::issue::ArchiveResult {
+            number_of_issues_updated: updated.len() as u64,
+            updated: Some(updated),
+            errors: Some(domains::JsonValue(errors.into())),
+        })
+    }
+}
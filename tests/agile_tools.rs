@@ -0,0 +1,280 @@
+mod common;
+
+use common::{MockJira, Reply, cloud};
+use serde_json::json;
+
+fn stub_board(mock: &MockJira) {
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board",
+        Reply::json(json!({ "values": [{ "id": 7, "name": "PROJ board" }] })),
+    );
+}
+
+#[tokio::test]
+async fn board_get_sprints_finds_board_by_project() {
+    let (mock, mut client) = cloud().await;
+    stub_board(&mock);
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board/7/sprint",
+        Reply::json(json!({ "values": [{ "id": 3, "name": "Sprint 3", "state": "active" }] })),
+    );
+
+    let out = client
+        .call_ok(
+            "board_get_sprints",
+            json!({ "projectKey": "PROJ", "state": "active" }),
+        )
+        .await;
+    assert_eq!(out["values"][0]["id"], 3);
+
+    let board = mock.only_request("GET", "/rest/agile/1.0/board");
+    assert_eq!(board.query_param("projectKeyOrId").as_deref(), Some("PROJ"));
+    let sprints = mock.only_request("GET", "/rest/agile/1.0/board/7/sprint");
+    assert_eq!(sprints.query_param("state").as_deref(), Some("active"));
+}
+
+#[tokio::test]
+async fn board_get_backlog_requires_board_or_project() {
+    let (mock, mut client) = cloud().await;
+    stub_board(&mock);
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board/7/backlog",
+        Reply::json(json!({ "issues": [{ "key": "PROJ-5" }] })),
+    );
+
+    let error = client.call_err("board_get_backlog", json!({})).await;
+    assert_eq!(error["kind"], "validation");
+    assert!(mock.requests().is_empty());
+
+    let out = client
+        .call_ok("board_get_backlog", json!({ "boardName": "PROJ board" }))
+        .await;
+    assert_eq!(out["issues"][0]["key"], "PROJ-5");
+    let board = mock.only_request("GET", "/rest/agile/1.0/board");
+    assert_eq!(board.query_param("name").as_deref(), Some("PROJ board"));
+}
+
+#[tokio::test]
+async fn board_get_backlog_pages_and_filters() {
+    let (mock, mut client) = cloud().await;
+    stub_board(&mock);
+    mock.on_fn("GET", "/rest/agile/1.0/board/7/backlog", |request| {
+        let start: usize = request
+            .query_param("startAt")
+            .map_or(0, |s| s.parse().unwrap());
+        let size: usize = request.query_param("maxResults").unwrap().parse().unwrap();
+        let issues: Vec<_> = (start..(start + size).min(120))
+            .map(|n| json!({ "key": format!("PROJ-{}", n) }))
+            .collect();
+        Reply::json(json!({ "startAt": start, "maxResults": size, "total": 120, "issues": issues }))
+    });
+
+    let out = client
+        .call_ok(
+            "board_get_backlog",
+            json!({ "boardName": "PROJ board", "startAt": 10, "limit": 5, "filter": "basic" }),
+        )
+        .await;
+    assert_eq!(out["issues"].as_array().unwrap().len(), 5);
+    assert_eq!(out["issues"][0]["key"], "PROJ-10");
+    let page = mock.only_request("GET", "/rest/agile/1.0/board/7/backlog");
+    assert_eq!(page.query_param("startAt").as_deref(), Some("10"));
+    assert_eq!(page.query_param("maxResults").as_deref(), Some("5"));
+    assert_eq!(
+        page.query_param("fields").as_deref(),
+        Some("id,key,summary,status")
+    );
+
+    let out = client
+        .call_ok(
+            "board_get_backlog",
+            json!({ "boardName": "PROJ board", "allPages": true }),
+        )
+        .await;
+    assert_eq!(out["issues"].as_array().unwrap().len(), 120);
+    assert_eq!(out["issues"][119]["key"], "PROJ-119");
+    let starts: Vec<_> = mock
+        .requests_to("GET", "/rest/agile/1.0/board/7/backlog")
+        .iter()
+        .skip(1)
+        .map(|r| r.query_param("startAt"))
+        .collect();
+    assert_eq!(
+        starts,
+        [None, Some("50".to_string()), Some("100".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn board_get_sprints_collects_pages_up_to_the_limit() {
+    let (mock, mut client) = cloud().await;
+    stub_board(&mock);
+    mock.on_fn("GET", "/rest/agile/1.0/board/7/sprint", |request| {
+        let start: i64 = request
+            .query_param("startAt")
+            .map_or(0, |s| s.parse().unwrap());
+        let size: i64 = request.query_param("maxResults").unwrap().parse().unwrap();
+        let values: Vec<_> = (start..(start + size).min(80))
+            .map(|n| json!({ "id": n, "name": format!("Sprint {}", n) }))
+            .collect();
+        Reply::json(json!({ "startAt": start, "isLast": start + size >= 80, "values": values }))
+    });
+
+    let out = client
+        .call_ok(
+            "board_get_sprints",
+            json!({ "projectKey": "PROJ", "allPages": true, "limit": 60 }),
+        )
+        .await;
+    assert_eq!(out["values"].as_array().unwrap().len(), 60);
+    assert_eq!(out["values"][59]["id"], 59);
+    assert_eq!(out["isLast"], false);
+    let sizes: Vec<_> = mock
+        .requests_to("GET", "/rest/agile/1.0/board/7/sprint")
+        .iter()
+        .map(|r| r.query_param("maxResults").unwrap())
+        .collect();
+    assert_eq!(sizes, ["50", "10"]);
+}
+
+#[tokio::test]
+async fn board_lookup_reports_missing_board() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board",
+        Reply::json(json!({ "values": [] })),
+    );
+
+    let error = client
+        .call_err("board_get_sprints", json!({ "projectKey": "NOPE" }))
+        .await;
+    assert_eq!(error["kind"], "not_found");
+    assert_eq!(error["resource"], "Board");
+    assert_eq!(error["name"], "NOPE");
+}
+
+#[tokio::test]
+async fn agile_rank_issues_sends_rank_anchor() {
+    let (mock, mut client) = cloud().await;
+    mock.on("PUT", "/rest/agile/1.0/issue/rank", Reply::no_content());
+
+    client
+        .call_ok(
+            "agile_rank_issues",
+            json!({ "issueKeys": ["PROJ-3", "PROJ-4"], "beforeIssueKey": "PROJ-1" }),
+        )
+        .await;
+    let body = mock.only_request("PUT", "/rest/agile/1.0/issue/rank").body;
+    assert_eq!(
+        body,
+        json!({ "issues": ["PROJ-3", "PROJ-4"], "rankBeforeIssue": "PROJ-1" })
+    );
+}
+
+#[tokio::test]
+async fn sprint_create_and_add_issues() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/agile/1.0/sprint",
+        Reply::status(
+            201,
+            json!({ "id": 12, "name": "Sprint 12", "state": "future" }),
+        ),
+    );
+    mock.on(
+        "POST",
+        "/rest/agile/1.0/sprint/12/issue",
+        Reply::no_content(),
+    );
+
+    let sprint = client
+        .call_ok(
+            "sprint_create",
+            json!({ "boardId": 7, "name": "Sprint 12", "goal": "Ship login" }),
+        )
+        .await;
+    assert_eq!(sprint["id"], 12);
+    let body = mock.only_request("POST", "/rest/agile/1.0/sprint").body;
+    assert_eq!(
+        body,
+        json!({ "originBoardId": 7, "name": "Sprint 12", "goal": "Ship login" })
+    );
+
+    client
+        .call_ok(
+            "sprint_add_issues",
+            json!({ "sprintId": 12, "issueKeys": ["PROJ-1"] }),
+        )
+        .await;
+    let body = mock
+        .only_request("POST", "/rest/agile/1.0/sprint/12/issue")
+        .body;
+    assert_eq!(body, json!({ "issues": ["PROJ-1"] }));
+}
+
+#[tokio::test]
+async fn sprint_update_merges_current_values() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/sprint/12",
+        Reply::json(json!({
+            "id": 12,
+            "name": "Sprint 12",
+            "state": "future",
+            "goal": "Ship login",
+            "startDate": "2026-01-05T09:00:00.000Z"
+        })),
+    );
+    mock.on_fn("PUT", "/rest/agile/1.0/sprint/12", |req| {
+        Reply::json(req.body.clone())
+    });
+
+    let out = client
+        .call_ok(
+            "sprint_update",
+            json!({ "sprintId": 12, "state": "active", "endDate": "2026-01-19T17:00:00.000Z" }),
+        )
+        .await;
+    assert_eq!(
+        out,
+        json!({
+            "name": "Sprint 12",
+            "state": "active",
+            "goal": "Ship login",
+            "startDate": "2026-01-05T09:00:00.000Z",
+            "endDate": "2026-01-19T17:00:00.000Z"
+        })
+    );
+}
+
+#[tokio::test]
+async fn sprint_update_validates_name_before_calling_jira() {
+    let (mock, mut client) = cloud().await;
+
+    let error = client
+        .call_err(
+            "sprint_update",
+            json!({ "sprintId": 12, "name": "A sprint name that is far too long for Jira" }),
+        )
+        .await;
+    assert_eq!(error["kind"], "validation");
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn sprint_delete() {
+    let (mock, mut client) = cloud().await;
+    mock.on("DELETE", "/rest/agile/1.0/sprint/12", Reply::no_content());
+
+    let out = client
+        .call_ok("sprint_delete", json!({ "sprintId": 12 }))
+        .await;
+    assert_eq!(out["success"], true);
+    mock.only_request("DELETE", "/rest/agile/1.0/sprint/12");
+}
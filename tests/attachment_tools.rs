@@ -0,0 +1,410 @@
+mod common;
+
+use common::{McpClient, MockJira, Reply, cloud, site};
+use jira_rs::attachments::AttachmentsDir;
+use jira_rs::jira::Jira;
+use jira_rs::site::Deployment;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Fresh directory under the system temp dir, unique per test.
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("jira-rs-attachments-{}", std::process::id()))
+        .join(test);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A client whose attachment tools may use files in `dir`.
+async fn with_dir(mock: &MockJira, deployment: Deployment, dir: &PathBuf) -> McpClient {
+    let mut sites = BTreeMap::new();
+    sites.insert("default".to_string(), site(mock, deployment));
+    let jira =
+        Jira::new(sites, "default").with_attachments_dir(Some(AttachmentsDir::new(dir).unwrap()));
+    McpClient::connect(jira).await
+}
+
+fn attachment(mock: &MockJira, id: &str, filename: &str, mime_type: &str) -> Value {
+    json!({
+        "id": id,
+        "filename": filename,
+        "author": { "accountId": "acc-1", "displayName": "Ana Lopez" },
+        "created": "2025-01-10T10:00:00.000+0000",
+        "size": 11,
+        "mimeType": mime_type,
+        // The site host, which the server must not call directly
+        "content": format!("https://example.atlassian.net/rest/api/3/attachment/content/{}", id),
+        "thumbnail": format!("https://example.atlassian.net/rest/api/3/attachment/thumbnail/{}", id),
+        "self": format!("{}/rest/api/3/attachment/{}", mock.url(), id)
+    })
+}
+
+#[tokio::test]
+async fn files_are_attached_from_a_path_or_base64() {
+    let mock = MockJira::start().await;
+    let dir = temp_dir("attach");
+    let mut client = with_dir(&mock, Deployment::Cloud, &dir).await;
+    let reply = Reply::json(json!([attachment(&mock, "10", "crash.log", "text/plain")]));
+    mock.on("POST", "/rest/api/3/issue/PROJ-1/attachments", reply);
+    std::fs::write(dir.join("crash.log"), "panic at 42").unwrap();
+
+    let out = client
+        .call_ok(
+            "issue_attach_file",
+            json!({ "issueKey": "PROJ-1", "path": "crash.log" }),
+        )
+        .await;
+    assert_eq!(out["attachments"][0]["id"], "10");
+    assert_eq!(out["attachments"][0]["author"]["displayName"], "Ana Lopez");
+
+    let upload = mock.only_request("POST", "/rest/api/3/issue/PROJ-1/attachments");
+    assert_eq!(upload.header("X-Atlassian-Token"), Some("no-check"));
+    assert!(
+        upload
+            .header("Content-Type")
+            .unwrap()
+            .starts_with("multipart/form-data; boundary=")
+    );
+    let body = String::from_utf8_lossy(&upload.raw_body);
+    assert!(
+        body.contains("name=\"file\"; filename=\"crash.log\""),
+        "{}",
+        body
+    );
+    assert!(body.contains("Content-Type: text/plain"));
+    assert!(body.contains("panic at 42"));
+
+    client
+        .call_ok(
+            "issue_attach_file",
+            json!({ "issueKey": "PROJ-1", "contentBase64": "iVBORw0KGgo=", "fileName": "screen.png" }),
+        )
+        .await;
+    let uploads = mock.requests_to("POST", "/rest/api/3/issue/PROJ-1/attachments");
+    let body = &uploads[1].raw_body;
+    let png = b"\x89PNG\r\n\x1a\n";
+    assert!(body.windows(png.len()).any(|w| w == png));
+    assert!(String::from_utf8_lossy(body).contains("Content-Type: image/png"));
+}
+
+#[tokio::test]
+async fn attach_rejects_missing_or_ambiguous_content() {
+    let mock = MockJira::start().await;
+    let dir = temp_dir("reject");
+    let mut client = with_dir(&mock, Deployment::Cloud, &dir).await;
+
+    for args in [
+        json!({ "issueKey": "PROJ-1" }),
+        json!({ "issueKey": "PROJ-1", "path": "a.log", "contentBase64": "YQ==" }),
+        json!({ "issueKey": "PROJ-1", "contentBase64": "YQ==" }),
+        json!({ "issueKey": "PROJ-1", "contentBase64": "not base64!", "fileName": "a.txt" }),
+        json!({ "issueKey": "PROJ-1", "path": "missing.log" }),
+    ] {
+        let error = client.call_err("issue_attach_file", args).await;
+        assert_eq!(error["kind"], "validation", "{}", error);
+    }
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn attachments_are_listed_and_downloaded() {
+    let mock = MockJira::start().await;
+    let dir = temp_dir("download");
+    let mut client = with_dir(&mock, Deployment::DataCenter, &dir).await;
+    let log = attachment(&mock, "10", "crash.log", "text/plain");
+    mock.on(
+        "GET",
+        "/rest/api/2/issue/PROJ-1",
+        Reply::json(json!({ "key": "PROJ-1", "fields": { "attachment": [log.clone()] } })),
+    );
+    mock.on("GET", "/rest/api/2/attachment/10", Reply::json(log));
+    mock.on(
+        "GET",
+        "/rest/api/2/attachment/content/10",
+        Reply::bytes("text/plain", b"panic at 42"),
+    );
+
+    let out = client
+        .call_ok("issue_list_attachments", json!({ "issueKey": "PROJ-1" }))
+        .await;
+    assert_eq!(out["attachments"][0]["filename"], "crash.log");
+    assert_eq!(out["attachments"][0]["size"], 11);
+    let request = mock.only_request("GET", "/rest/api/2/issue/PROJ-1");
+    assert_eq!(request.query_param("fields").as_deref(), Some("attachment"));
+
+    // Into a directory, under the attachment's name
+    let out = client
+        .call_ok(
+            "attachment_download",
+            json!({ "attachmentId": "10", "path": "." }),
+        )
+        .await;
+    let written = dir.canonicalize().unwrap().join("crash.log");
+    assert_eq!(out["path"], written.to_str().unwrap());
+    assert_eq!(out["size"], 11);
+    assert_eq!(out["mimeType"], "text/plain");
+    assert_eq!(std::fs::read(&written).unwrap(), b"panic at 42");
+
+    // Existing files are only replaced on request
+    let error = client
+        .call_err(
+            "attachment_download",
+            json!({ "attachmentId": "10", "path": written.to_str().unwrap() }),
+        )
+        .await;
+    assert_eq!(error["kind"], "validation");
+    assert!(error["message"].as_str().unwrap().contains("overwrite"));
+    std::fs::write(&written, "old").unwrap();
+    client
+        .call_ok(
+            "attachment_download",
+            json!({ "attachmentId": "10", "path": written.to_str().unwrap(), "overwrite": true }),
+        )
+        .await;
+    assert_eq!(std::fs::read(&written).unwrap(), b"panic at 42");
+}
+
+#[tokio::test]
+async fn attachment_delete_previews_on_dry_run() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/10",
+        Reply::json(attachment(&mock, "10", "crash.log", "text/plain")),
+    );
+    mock.on("DELETE", "/rest/api/3/attachment/10", Reply::no_content());
+
+    let out = client
+        .call_ok(
+            "attachment_delete",
+            json!({ "attachmentId": "10", "dryRun": true }),
+        )
+        .await;
+    assert_eq!(out["dryRun"], true);
+    assert_eq!(out["preview"]["attachment"]["filename"], "crash.log");
+    assert!(
+        mock.requests_to("DELETE", "/rest/api/3/attachment/10")
+            .is_empty()
+    );
+
+    let token = out["confirmToken"].as_str().unwrap().to_string();
+    let out = client
+        .call_ok(
+            "attachment_delete",
+            json!({ "attachmentId": "10", "confirmToken": token }),
+        )
+        .await;
+    assert_eq!(out["success"], true);
+    mock.only_request("DELETE", "/rest/api/3/attachment/10");
+}
+
+async fn read(client: &mut McpClient, arguments: Value) -> Value {
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "attachment_read", "arguments": arguments }),
+        )
+        .await;
+    let result = response["result"].clone();
+    assert_eq!(result["isError"], false, "{}", response);
+    result
+}
+
+#[tokio::test]
+async fn images_are_read_as_image_content() {
+    let (mock, mut client) = cloud().await;
+    let png = b"\x89PNG\r\n\x1a\nsmall";
+    let mut small = attachment(&mock, "20", "screen.png", "image/png");
+    small["size"] = json!(png.len());
+    let mut large = attachment(&mock, "21", "full.jpg", "image/jpeg");
+    large["size"] = json!(5_000_000);
+    mock.on("GET", "/rest/api/3/attachment/20", Reply::json(small));
+    mock.on("GET", "/rest/api/3/attachment/21", Reply::json(large));
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/content/20",
+        Reply::bytes("image/png", png),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/thumbnail/21",
+        Reply::bytes("image/png", b"thumb"),
+    );
+
+    let result = read(&mut client, json!({ "attachmentId": "20" })).await;
+    assert_eq!(result["structuredContent"]["kind"], "image");
+    assert_eq!(result["structuredContent"]["thumbnail"], false);
+    assert_eq!(
+        result["content"][1],
+        json!({ "type": "image", "data": "iVBORw0KGgpzbWFsbA==", "mimeType": "image/png" })
+    );
+
+    // Large images are replaced by Jira's thumbnail
+    let result = read(&mut client, json!({ "attachmentId": "21" })).await;
+    assert_eq!(result["structuredContent"]["thumbnail"], true);
+    assert_eq!(result["structuredContent"]["mimeType"], "image/png");
+    assert_eq!(result["content"][1]["data"], "dGh1bWI=");
+    assert!(
+        mock.requests_to("GET", "/rest/api/3/attachment/content/21")
+            .is_empty()
+    );
+
+    // Without a thumbnail only the description comes back
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/thumbnail/21",
+        Reply::status(404, json!({ "errorMessages": ["No thumbnail"] })),
+    );
+    let result = read(&mut client, json!({ "attachmentId": "21" })).await;
+    assert_eq!(result["content"].as_array().unwrap().len(), 1);
+    assert!(
+        result["structuredContent"]["note"]
+            .as_str()
+            .unwrap()
+            .contains("no thumbnail")
+    );
+}
+
+#[tokio::test]
+async fn text_attachments_are_read_up_to_max_bytes() {
+    let (mock, mut client) = cloud().await;
+    let mut log = attachment(&mock, "10", "crash.log", "application/octet-stream");
+    log["size"] = json!(11);
+    mock.on("GET", "/rest/api/3/attachment/10", Reply::json(log));
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/content/10",
+        Reply::bytes("application/octet-stream", b"panic at 42"),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/30",
+        Reply::json(attachment(&mock, "30", "dump.zip", "application/zip")),
+    );
+
+    let result = read(&mut client, json!({ "attachmentId": "10" })).await;
+    assert_eq!(result["structuredContent"]["text"], "panic at 42");
+    assert_eq!(result["structuredContent"]["truncated"], false);
+    assert_eq!(result["structuredContent"]["mimeType"], "text/plain");
+
+    let result = read(&mut client, json!({ "attachmentId": "10", "maxBytes": 5 })).await;
+    let content = &result["structuredContent"];
+    assert_eq!(content["truncated"], true);
+    let text = content["text"].as_str().unwrap();
+    assert!(
+        text.starts_with("panic\n[... truncated after 5 bytes of 11;"),
+        "{}",
+        text
+    );
+    let downloads = mock.requests_to("GET", "/rest/api/3/attachment/content/10");
+    assert_eq!(downloads[1].header("Range"), Some("bytes=0-5"));
+
+    // Other files are left to attachment_download
+    let error = client
+        .call_err("attachment_read", json!({ "attachmentId": "30" }))
+        .await;
+    assert_eq!(error["kind"], "validation");
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .contains("application/zip")
+    );
+}
+
+#[tokio::test]
+async fn local_paths_stay_inside_the_attachments_directory() {
+    let mock = MockJira::start().await;
+    let root = temp_dir("confined");
+    let dir = root.join("attachments");
+    let mut client = with_dir(&mock, Deployment::Cloud, &dir).await;
+    std::fs::write(root.join("secret.toml"), "token = 'x'").unwrap();
+    mock.on(
+        "GET",
+        "/rest/api/3/attachment/10",
+        Reply::json(attachment(&mock, "10", "../crash.log", "text/plain")),
+    );
+
+    let secret = root.join("secret.toml");
+    for path in ["../secret.toml", secret.to_str().unwrap()] {
+        let error = client
+            .call_err(
+                "issue_attach_file",
+                json!({ "issueKey": "PROJ-1", "path": path }),
+            )
+            .await;
+        assert!(
+            error["message"]
+                .as_str()
+                .unwrap()
+                .contains("outside the attachments directory"),
+            "{}",
+            error
+        );
+    }
+    for path in ["../evil.log", root.join("evil.log").to_str().unwrap()] {
+        let error = client
+            .call_err(
+                "attachment_download",
+                json!({ "attachmentId": "10", "path": path }),
+            )
+            .await;
+        assert_eq!(error["kind"], "validation");
+    }
+    assert!(!root.join("evil.log").exists());
+
+    // A dangling symlink inside the directory must not be written through
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(root.join("planted.log"), dir.join("link.log")).unwrap();
+        for overwrite in [false, true] {
+            let error = client
+                .call_err(
+                    "attachment_download",
+                    json!({ "attachmentId": "10", "path": "link.log", "overwrite": overwrite }),
+                )
+                .await;
+            assert!(
+                error["message"].as_str().unwrap().contains("symbolic link"),
+                "{}",
+                error
+            );
+        }
+        assert!(!root.join("planted.log").exists());
+    }
+    assert!(
+        mock.requests_to("POST", "/rest/api/3/issue/PROJ-1/attachments")
+            .is_empty()
+    );
+
+    // Without a directory, local paths are refused altogether
+    let (mock, mut client) = cloud().await;
+    let error = client
+        .call_err(
+            "issue_attach_file",
+            json!({ "issueKey": "PROJ-1", "path": secret.to_str().unwrap() }),
+        )
+        .await;
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .contains("JIRA_ATTACHMENTS_DIR")
+    );
+    let error = client
+        .call_err(
+            "attachment_download",
+            json!({ "attachmentId": "10", "path": "crash.log" }),
+        )
+        .await;
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .contains("JIRA_ATTACHMENTS_DIR")
+    );
+    assert!(mock.requests().is_empty());
+}
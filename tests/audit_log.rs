@@ -0,0 +1,210 @@
+mod common;
+
+use common::{McpClient, MockJira, Reply, cloud, site};
+use jira_rs::audit::AuditLog;
+use jira_rs::jira::Jira;
+use jira_rs::site::Deployment;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Fresh log file under the system temp dir, unique per test.
+fn log_path(test: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("jira-rs-audit-{}", std::process::id()))
+        .join(format!("{}.jsonl", test));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+async fn audited(mock: &MockJira, path: &PathBuf) -> McpClient {
+    let mut sites = BTreeMap::new();
+    sites.insert("default".to_string(), site(mock, Deployment::Cloud));
+    let jira = Jira::new(sites, "default").with_audit_log(Some(AuditLog::new(path)));
+    McpClient::connect(jira).await
+}
+
+fn lines(path: &PathBuf) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn mutating_requests_are_appended_to_the_log() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({ "id": "1", "key": "PROJ-1", "self": "", "fields": {} })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/comment",
+        Reply::status(201, json!({ "id": "500" })),
+    );
+    mock.on(
+        "PUT",
+        "/rest/api/3/issue/PROJ-2/assignee",
+        Reply::status(400, json!({ "errorMessages": ["User cannot be assigned"] })),
+    );
+    let path = log_path("appended");
+    let mut client = audited(&mock, &path).await;
+
+    client
+        .call_ok("issue_get", json!({ "issueKey": "PROJ-1" }))
+        .await;
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "Looks good" }),
+        )
+        .await;
+    client
+        .call_err(
+            "issue_assign",
+            json!({ "issueKey": "PROJ-2", "assignee": "acc-1" }),
+        )
+        .await;
+
+    // Reads are not audited
+    let entries = lines(&path);
+    assert_eq!(entries.len(), 2);
+    let comment = &entries[0];
+    assert_eq!(comment["tool"], "issue_add_comment");
+    assert_eq!(
+        comment["arguments"],
+        json!({ "issueKey": "PROJ-1", "comment": "Looks good" })
+    );
+    assert_eq!(comment["issueKeys"], json!(["PROJ-1"]));
+    assert_eq!(comment["method"], "POST");
+    assert_eq!(
+        comment["url"],
+        format!("{}/rest/api/3/issue/PROJ-1/comment", mock.url())
+    );
+    assert_eq!(comment["status"], 201);
+    assert!(comment["timestamp"].is_string());
+
+    // Failed changes are recorded with Jira's status
+    assert_eq!(entries[1]["tool"], "issue_assign");
+    assert_eq!(entries[1]["method"], "PUT");
+    assert_eq!(entries[1]["status"], 400);
+}
+
+#[tokio::test]
+async fn audit_log_query_filters_newest_first() {
+    let mock = MockJira::start().await;
+    for key in ["PROJ-1", "PROJ-2"] {
+        mock.on(
+            "POST",
+            &format!("/rest/api/3/issue/{}/comment", key),
+            Reply::status(201, json!({ "id": "500" })),
+        );
+        mock.on(
+            "PUT",
+            &format!("/rest/api/3/issue/{}/assignee", key),
+            Reply::no_content(),
+        );
+    }
+    let path = log_path("query");
+    let mut client = audited(&mock, &path).await;
+
+    for key in ["PROJ-1", "PROJ-2"] {
+        client
+            .call_ok(
+                "issue_add_comment",
+                json!({ "issueKey": key, "comment": "Done" }),
+            )
+            .await;
+        client
+            .call_ok(
+                "issue_assign",
+                json!({ "issueKey": key, "assignee": "acc-1" }),
+            )
+            .await;
+    }
+
+    let out = client
+        .call_ok("audit_log_query", json!({ "issueKey": "PROJ-1" }))
+        .await;
+    assert_eq!(out["path"], path.to_str().unwrap());
+    let tools: Vec<_> = out["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["tool"].clone())
+        .collect();
+    assert_eq!(tools, [json!("issue_assign"), json!("issue_add_comment")]);
+
+    let out = client
+        .call_ok(
+            "audit_log_query",
+            json!({ "tool": "issue_add_comment", "limit": 1 }),
+        )
+        .await;
+    let entries = out["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["issueKeys"], json!(["PROJ-2"]));
+
+    let out = client
+        .call_ok(
+            "audit_log_query",
+            json!({ "since": "2999-01-01T00:00:00Z" }),
+        )
+        .await;
+    assert_eq!(out["entries"], json!([]));
+
+    let error = client
+        .call_err("audit_log_query", json!({ "since": "yesterday" }))
+        .await;
+    assert_eq!(error["kind"], "validation");
+}
+
+#[tokio::test]
+async fn audit_log_query_fails_when_auditing_is_disabled() {
+    let (_mock, mut client) = cloud().await;
+
+    let error = client.call_err("audit_log_query", json!({})).await;
+    assert_eq!(error["kind"], "validation");
+}
+
+#[tokio::test]
+async fn large_arguments_are_summarized_in_the_log() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/attachments",
+        Reply::json(json!([])),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/comment",
+        Reply::status(201, json!({ "id": "500" })),
+    );
+    let path = log_path("summarized");
+    let mut client = audited(&mock, &path).await;
+
+    // 3000 bytes of base64 encode 2250 bytes
+    client
+        .call_ok(
+            "issue_attach_file",
+            json!({ "issueKey": "PROJ-1", "contentBase64": "QUJD".repeat(750), "fileName": "a.txt" }),
+        )
+        .await;
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "x".repeat(5000) }),
+        )
+        .await;
+
+    let entries = lines(&path);
+    assert_eq!(
+        entries[0]["arguments"],
+        json!({ "issueKey": "PROJ-1", "contentBase64": "<base64, 2250 bytes>", "fileName": "a.txt" })
+    );
+    assert_eq!(entries[0]["issueKeys"], json!(["PROJ-1"]));
+    assert_eq!(entries[1]["arguments"]["comment"], "<text, 5000 bytes>");
+}
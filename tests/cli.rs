@@ -0,0 +1,175 @@
+mod common;
+
+use common::{MockJira, Reply, site};
+use jira_rs::cli::{self, Command, Format};
+use jira_rs::jira::Jira;
+use jira_rs::site::Deployment;
+use rmcp::model::Tool;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+fn tools() -> Vec<Tool> {
+    Jira::new(BTreeMap::new(), "default").tool_router.list_all()
+}
+
+fn parse(line: &str) -> anyhow::Result<Command> {
+    let args: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+    cli::parse(&tools(), &args)
+}
+
+fn call(line: &str) -> (String, Value, Format) {
+    match parse(line).unwrap() {
+        Command::Call {
+            tool,
+            arguments,
+            format,
+        } => (tool, Value::Object(arguments), format),
+        Command::Help(text) => panic!("expected a call, got help: {}", text),
+    }
+}
+
+fn jira(mock: &MockJira) -> Jira {
+    let mut sites = BTreeMap::new();
+    sites.insert("default".to_string(), site(mock, Deployment::Cloud));
+    Jira::new(sites, "default")
+}
+
+#[test]
+fn words_name_the_tool_and_fill_required_arguments() {
+    let (tool, arguments, format) = call("issue get PROJ-1 --filter basic");
+    assert_eq!(tool, "issue_get");
+    assert_eq!(
+        arguments,
+        json!({ "issueKey": "PROJ-1", "filter": "basic" })
+    );
+    assert_eq!(format, Format::Json);
+
+    // Tool names work as-is, and a group with a single tool by its first word
+    assert_eq!(call("issue_get PROJ-1").0, "issue_get");
+    let (tool, arguments, format) = call("search --jql project=PROJ --limit 5 -o table");
+    assert_eq!(tool, "search_issues");
+    assert_eq!(arguments, json!({ "jql": "project=PROJ", "limit": 5 }));
+    assert_eq!(format, Format::Table);
+}
+
+#[test]
+fn flags_are_typed_from_the_schema() {
+    let (_, arguments, _) =
+        call("issue delete --issueKey PROJ-1 --dry-run --delete_subtasks false");
+    assert_eq!(
+        arguments,
+        json!({ "issueKey": "PROJ-1", "dryRun": true, "deleteSubtasks": false })
+    );
+
+    // Arrays take comma-separated or repeated values
+    let (_, arguments, _) = call("issue archive --issue-keys PROJ-1,PROJ-2 --issue-keys PROJ-3");
+    assert_eq!(
+        arguments,
+        json!({ "issueKeys": ["PROJ-1", "PROJ-2", "PROJ-3"] })
+    );
+    let (_, arguments, _) = call("issue edit details PROJ-1 --labels=");
+    assert_eq!(arguments, json!({ "issueKey": "PROJ-1", "labels": [] }));
+}
+
+#[test]
+fn usage_errors_point_to_help() {
+    let error = parse("issue frobnicate").unwrap_err().to_string();
+    assert!(
+        error.contains("Unknown command 'issue frobnicate'"),
+        "{}",
+        error
+    );
+
+    let error = parse("issue get").unwrap_err().to_string();
+    assert!(error.contains("Missing <issue-key>"), "{}", error);
+
+    let error = parse("issue get PROJ-1 --nope 1").unwrap_err().to_string();
+    assert!(error.contains("Unknown option --nope"), "{}", error);
+
+    let error = parse("search --limit many").unwrap_err().to_string();
+    assert!(error.contains("--limit expects an integer"), "{}", error);
+
+    let Command::Help(text) = parse("help").unwrap() else {
+        panic!("expected help");
+    };
+    assert!(text.contains("issue get"), "{}", text);
+    let Command::Help(text) = parse("issue get --help").unwrap() else {
+        panic!("expected help");
+    };
+    assert!(
+        text.contains("Usage: jira-rs issue get <issue-key>"),
+        "{}",
+        text
+    );
+    assert!(text.contains("--filter <string>"), "{}", text);
+}
+
+#[tokio::test]
+async fn calls_run_the_same_tool_logic_as_the_server() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({
+            "id": "1",
+            "key": "PROJ-1",
+            "self": "https://example.atlassian.net/rest/api/3/issue/1",
+            "fields": { "summary": "Hello" }
+        })),
+    );
+
+    let (tool, arguments, _) = call("issue get PROJ-1 --filter basic");
+    let Value::Object(arguments) = arguments else {
+        unreachable!()
+    };
+    let output = cli::call(jira(&mock), &tool, arguments).await.unwrap();
+    assert!(!output.is_error, "{}", output.text);
+    let value: Value = serde_json::from_str(&output.text).unwrap();
+    assert_eq!(value["key"], "PROJ-1");
+    assert_eq!(
+        mock.only_request("GET", "/rest/api/3/issue/PROJ-1")
+            .query_param("fields")
+            .as_deref(),
+        Some("id,key,summary,status")
+    );
+
+    // Tool errors come back flagged, invalid calls as errors
+    let mut arguments = Map::new();
+    arguments.insert("issueKey".to_string(), json!("PROJ-404"));
+    let output = cli::call(jira(&mock), "issue_get", arguments)
+        .await
+        .unwrap();
+    assert!(output.is_error);
+    assert!(output.text.contains("HTTP 404"), "{}", output.text);
+    assert!(
+        cli::call(jira(&mock), "issue_get", Map::new())
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+fn table_output_lists_records() {
+    let results = json!({
+        "issues": [
+            {
+                "key": "PROJ-1",
+                "self": "https://example.atlassian.net/rest/api/3/issue/1",
+                "fields": { "summary": "First", "status": { "name": "To Do" } }
+            },
+            {
+                "key": "PROJ-2",
+                "fields": { "summary": "Second", "status": { "name": "Done" }, "labels": ["a", "b"] }
+            }
+        ],
+        "isLast": true
+    });
+    let table = cli::render(&results, Format::Table);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "key     status  summary  labels");
+    assert_eq!(lines[2], "PROJ-1  To Do   First");
+    assert_eq!(lines[3], "PROJ-2  Done    Second   a, b");
+
+    let table = cli::render(&json!({ "success": true, "message": "ok" }), Format::Table);
+    assert!(table.lines().any(|l| l == "message  ok"), "{}", table);
+}
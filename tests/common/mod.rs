@@ -0,0 +1,513 @@
+//! Shared harness for the end-to-end tests: an in-process HTTP stub that
+//! emulates the Jira endpoints the tools use, and a minimal MCP client that
+//! talks JSON-RPC to [`Jira`] over an in-memory duplex stream.
+
+#![allow(dead_code)]
+
+use jira_rs::auth::Auth;
+use jira_rs::jira::Jira;
+use jira_rs::retry::RetryPolicy;
+use jira_rs::site::{Deployment, Site};
+use rmcp::ServiceExt;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+// ============================================================================
+// MOCK JIRA
+// ============================================================================
+
+/// A request received by the mock, as the tools sent it.
+#[derive(Clone, Debug)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub headers: Vec<(String, String)>,
+    /// Parsed JSON body, `Value::Null` when the request had none.
+    pub body: Value,
+    /// The body as received, for requests that are not JSON.
+    pub raw_body: Vec<u8>,
+}
+
+impl Recorded {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Decoded value of a query parameter.
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.query.split('&').find_map(|pair| {
+            let (k, v) = pair.split_once('=')?;
+            (k == name).then(|| {
+                urlencoding::decode(&v.replace('+', " "))
+                    .map(|v| v.into_owned())
+                    .unwrap_or_default()
+            })
+        })
+    }
+}
+
+/// Response of a stubbed endpoint.
+#[derive(Clone, Debug)]
+pub struct Reply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+    /// Body sent as is instead of `body`, with its own content type.
+    pub raw: Option<Vec<u8>>,
+}
+
+impl Reply {
+    pub fn json(body: Value) -> Self {
+        Self::status(200, body)
+    }
+
+    pub fn status(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Some(body),
+            raw: None,
+        }
+    }
+
+    pub fn no_content() -> Self {
+        Self {
+            status: 204,
+            headers: Vec::new(),
+            body: None,
+            raw: None,
+        }
+    }
+
+    /// A 200 response carrying `data` as `content_type`.
+    pub fn bytes(content_type: &str, data: &[u8]) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: None,
+            raw: Some(data.to_vec()),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+type Handler = Box<dyn Fn(&Recorded) -> Reply + Send + Sync>;
+
+struct Route {
+    method: String,
+    path: String,
+    handler: Handler,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<Recorded>,
+}
+
+/// In-process Jira stub. Routes match on method and exact path (query
+/// excluded); the most recently registered route wins. Unknown routes answer
+/// 404 with a Jira style error body.
+#[derive(Clone)]
+pub struct MockJira {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockJira {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let accept_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    let _ = serve_connection(stream, state).await;
+                });
+            }
+        });
+
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Answers `method path` with a fixed reply.
+    pub fn on(&self, method: &str, path: &str, reply: Reply) -> &Self {
+        self.on_fn(method, path, move |_| reply.clone())
+    }
+
+    /// Answers `method path` with a reply computed from the request.
+    pub fn on_fn(
+        &self,
+        method: &str,
+        path: &str,
+        handler: impl Fn(&Recorded) -> Reply + Send + Sync + 'static,
+    ) -> &Self {
+        self.state.lock().unwrap().routes.push(Route {
+            method: method.to_string(),
+            path: path.to_string(),
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Requests received for `method path`.
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<Recorded> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path == path)
+            .collect()
+    }
+
+    /// The single request received for `method path`; panics otherwise.
+    pub fn only_request(&self, method: &str, path: &str) -> Recorded {
+        let mut matching = self.requests_to(method, path);
+        assert_eq!(
+            matching.len(),
+            1,
+            "expected exactly one {} {}, got {:?}",
+            method,
+            path,
+            self.requests()
+                .iter()
+                .map(|r| format!("{} {}", r.method, r.path))
+                .collect::<Vec<_>>()
+        );
+        matching.remove(0)
+    }
+
+    fn respond(&self, request: &Recorded) -> Reply {
+        let state = self.state.lock().unwrap();
+        state
+            .routes
+            .iter()
+            .rev()
+            .find(|r| r.method == request.method && r.path == request.path)
+            .map(|r| (r.handler)(request))
+            .unwrap_or_else(|| {
+                Reply::status(
+                    404,
+                    json!({ "errorMessages": [format!("No mock for {} {}", request.method, request.path)] }),
+                )
+            })
+    }
+}
+
+async fn serve_connection(stream: TcpStream, state: Arc<Mutex<State>>) -> std::io::Result<()> {
+    let mock = MockJira {
+        url: String::new(),
+        state,
+    };
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await? == 0 {
+            return Ok(());
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default().to_string();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target, String::new()),
+        };
+
+        let mut headers = Vec::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                let value = value.trim().to_string();
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.parse().unwrap_or(0);
+                }
+                headers.push((name.to_string(), value));
+            }
+        }
+
+        let mut raw_body = vec![0u8; content_length];
+        reader.read_exact(&mut raw_body).await?;
+        let body = serde_json::from_slice(&raw_body).unwrap_or(Value::Null);
+
+        let request = Recorded {
+            method,
+            path,
+            query,
+            headers,
+            body,
+            raw_body,
+        };
+        mock.state.lock().unwrap().requests.push(request.clone());
+        let reply = mock.respond(&request);
+
+        let payload = match reply.raw {
+            Some(raw) => raw,
+            None => reply
+                .body
+                .map(|b| b.to_string())
+                .unwrap_or_default()
+                .into_bytes(),
+        };
+        let mut response = format!(
+            "HTTP/1.1 {} MOCK\r\nContent-Length: {}\r\n",
+            reply.status,
+            payload.len()
+        );
+        if !reply
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        {
+            response.push_str("Content-Type: application/json\r\n");
+        }
+        for (name, value) in &reply.headers {
+            response.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        response.push_str("\r\n");
+
+        let stream = reader.get_mut();
+        stream.write_all(response.as_bytes()).await?;
+        stream.write_all(&payload).await?;
+        stream.flush().await?;
+    }
+}
+
+// ============================================================================
+// MCP CLIENT
+// ============================================================================
+
+/// Outcome of a `tools/call`.
+#[derive(Debug)]
+pub struct ToolResult {
+    pub is_error: bool,
+    pub text: String,
+}
+
+impl ToolResult {
+    /// The text content parsed as JSON.
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.text)
+            .unwrap_or_else(|e| panic!("tool output is not JSON ({}): {}", e, self.text))
+    }
+}
+
+/// JSON-RPC client speaking MCP to an in-process [`Jira`] server.
+pub struct McpClient {
+    writer: tokio::io::WriteHalf<tokio::io::DuplexStream>,
+    reader: BufReader<tokio::io::ReadHalf<tokio::io::DuplexStream>>,
+    next_id: u64,
+}
+
+impl McpClient {
+    /// Serves `jira` on an in-memory stream and completes the MCP handshake.
+    pub async fn connect(jira: Jira) -> Self {
+        let (client_io, server_io) = tokio::io::duplex(1 << 20);
+        tokio::spawn(async move {
+            if let Ok(service) = jira.serve(server_io).await {
+                let _ = service.waiting().await;
+            }
+        });
+
+        let (reader, writer) = tokio::io::split(client_io);
+        let mut client = Self {
+            writer,
+            reader: BufReader::new(reader),
+            next_id: 0,
+        };
+
+        let init = client
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": "2025-03-26",
+                    "capabilities": {},
+                    "clientInfo": { "name": "jira-rs-e2e", "version": "0.0.0" }
+                }),
+            )
+            .await;
+        assert!(init.get("result").is_some(), "initialize failed: {}", init);
+        client
+            .send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await;
+        client
+    }
+
+    async fn send(&mut self, message: &Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await.unwrap();
+        self.writer.flush().await.unwrap();
+    }
+
+    /// Sends a request and waits for the response with the same id.
+    pub async fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await;
+
+        let read = async {
+            loop {
+                let mut line = String::new();
+                let n = self.reader.read_line(&mut line).await.unwrap();
+                assert!(n > 0, "server closed the stream");
+                let message: Value = serde_json::from_str(&line).unwrap();
+                if message.get("id") == Some(&json!(id)) {
+                    return message;
+                }
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(10), read)
+            .await
+            .unwrap_or_else(|_| panic!("no response to {}", method))
+    }
+
+    /// Calls a tool and returns its text content.
+    pub async fn call(&mut self, tool: &str, arguments: Value) -> ToolResult {
+        let response = self
+            .request(
+                "tools/call",
+                json!({ "name": tool, "arguments": arguments }),
+            )
+            .await;
+        let result = response
+            .get("result")
+            .unwrap_or_else(|| panic!("tools/call {} failed: {}", tool, response));
+        let text = result["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|c| c.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        ToolResult {
+            is_error: result
+                .get("isError")
+                .and_then(|e| e.as_bool())
+                .unwrap_or(false),
+            text,
+        }
+    }
+
+    /// Calls a tool that is expected to succeed and returns its JSON output.
+    pub async fn call_ok(&mut self, tool: &str, arguments: Value) -> Value {
+        let result = self.call(tool, arguments).await;
+        assert!(!result.is_error, "{} failed: {}", tool, result.text);
+        result.json()
+    }
+
+    /// Calls a tool that is expected to fail and returns the `error` object.
+    pub async fn call_err(&mut self, tool: &str, arguments: Value) -> Value {
+        let result = self.call(tool, arguments).await;
+        assert!(
+            result.is_error,
+            "{} unexpectedly succeeded: {}",
+            tool, result.text
+        );
+        result.json()["error"].clone()
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Site pointing at the mock, without retry delays.
+pub fn site(mock: &MockJira, deployment: Deployment) -> Site {
+    Site::new(
+        mock.url(),
+        Auth::Basic {
+            username: "bot@example.com".to_string(),
+            password: "secret".to_string(),
+        },
+    )
+    .with_deployment(deployment)
+    .with_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(1),
+        ..RetryPolicy::default()
+    })
+}
+
+/// Mock Jira plus a connected client using a single `default` workspace.
+pub async fn setup(deployment: Deployment) -> (MockJira, McpClient) {
+    let mock = MockJira::start().await;
+    let mut sites = BTreeMap::new();
+    sites.insert("default".to_string(), site(&mock, deployment));
+    let client = McpClient::connect(Jira::new(sites, "default")).await;
+    (mock, client)
+}
+
+pub async fn cloud() -> (MockJira, McpClient) {
+    setup(Deployment::Cloud).await
+}
+
+pub async fn data_center() -> (MockJira, McpClient) {
+    setup(Deployment::DataCenter).await
+}
+
+// ============================================================================
+// FIXTURES
+// ============================================================================
+
+/// `/field` answer with the usual Story Points and Epic Link custom fields.
+pub fn fields() -> Value {
+    json!([
+        { "id": "summary", "name": "Summary", "custom": false, "schema": { "type": "string" } },
+        { "id": "status", "name": "Status", "custom": false, "schema": { "type": "status" } },
+        { "id": "customfield_10016", "name": "Story Points", "custom": true, "schema": { "type": "number" } },
+        { "id": "customfield_10026", "name": "Story point estimate", "custom": true, "schema": { "type": "number" } },
+        { "id": "customfield_10014", "name": "Epic Link", "custom": true, "schema": { "type": "any" } }
+    ])
+}
+
+/// Cloud createmeta answer for project `PROJ`.
+pub fn createmeta() -> Value {
+    json!({
+        "projects": [{
+            "key": "PROJ",
+            "issuetypes": [
+                { "id": "10001", "name": "Historia", "untranslatedName": "Story", "subtask": false },
+                { "id": "10002", "name": "Bug", "subtask": false },
+                { "id": "10003", "name": "Subtarea", "untranslatedName": "Sub-task", "subtask": true }
+            ]
+        }]
+    })
+}
+
+/// ADF document with a single paragraph, as `issue_*` tools send on Cloud.
+pub fn adf_paragraph(text: &str) -> Value {
+    json!({
+        "version": 1,
+        "type": "doc",
+        "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": text }] }]
+    })
+}
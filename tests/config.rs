@@ -0,0 +1,136 @@
+mod common;
+
+use common::{MockJira, Reply};
+use jira_rs::config::{ConfigFile, Profile, credentials_diagnostic, load_profiles};
+use jira_rs::retry::RetryPolicy;
+use jira_rs::site::Deployment;
+use serde_json::json;
+
+fn profile(mock: &MockJira, token: Option<&str>, token_command: Option<&str>) -> Profile {
+    Profile {
+        name: "default".to_string(),
+        base_url: Some(mock.url().to_string()),
+        workspace: None,
+        username: Some("bot@example.com".to_string()),
+        token: token.map(str::to_string),
+        token_command: token_command.map(str::to_string),
+        deployment: Some(Deployment::Cloud),
+        oauth: None,
+    }
+}
+
+#[test]
+fn config_file_defines_default_and_named_profiles() {
+    let file = ConfigFile::parse(
+        r#"
+        default_profile = "client"
+        workspace = "mycompany"
+        username = "me@example.com"
+        token_command = "pass show jira"
+
+        [profiles.client]
+        base_url = "https://jira.client.com"
+        token = "pat"
+        deployment = "datacenter"
+        "#,
+    )
+    .unwrap();
+
+    let (profiles, default) = load_profiles(Some(&file)).unwrap();
+    assert_eq!(default, "client");
+    assert_eq!(profiles.len(), 2);
+
+    let primary = &profiles[0];
+    assert_eq!(primary.name, "default");
+    assert_eq!(primary.workspace.as_deref(), Some("mycompany"));
+    assert_eq!(primary.username.as_deref(), Some("me@example.com"));
+    assert_eq!(primary.token_command.as_deref(), Some("pass show jira"));
+
+    let client = &profiles[1];
+    assert_eq!(client.name, "client");
+    assert_eq!(client.base_url.as_deref(), Some("https://jira.client.com"));
+    assert_eq!(client.token.as_deref(), Some("pat"));
+    assert_eq!(client.deployment, Some(Deployment::DataCenter));
+}
+
+#[test]
+fn missing_configuration_is_an_error_not_a_panic() {
+    let error = load_profiles(None).unwrap_err();
+    assert!(
+        error.to_string().contains("No Jira workspace configured"),
+        "{}",
+        error
+    );
+
+    let file = ConfigFile::parse("[profiles.client]\nusername = \"me\"\n").unwrap();
+    let error = load_profiles(Some(&file)).unwrap_err();
+    assert!(error.to_string().contains("'client'"), "{}", error);
+
+    let error =
+        ConfigFile::load(Some(std::path::Path::new("/nonexistent/config.toml"))).unwrap_err();
+    assert!(
+        error.to_string().contains("/nonexistent/config.toml"),
+        "{}",
+        error
+    );
+    assert!(ConfigFile::parse("workspace = ").is_err());
+}
+
+#[tokio::test]
+async fn token_command_output_is_used_as_the_token() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/myself",
+        Reply::json(json!({ "accountId": "acc-1", "displayName": "Bot" })),
+    );
+
+    let site = profile(&mock, None, Some("echo '  s3cret  '"))
+        .connect(&RetryPolicy::default())
+        .await
+        .unwrap();
+    site.myself().await.unwrap();
+
+    let request = mock.only_request("GET", "/rest/api/3/myself");
+    // base64("bot@example.com:s3cret")
+    assert_eq!(
+        request.header("authorization"),
+        Some("Basic Ym90QGV4YW1wbGUuY29tOnMzY3JldA==")
+    );
+}
+
+#[tokio::test]
+async fn failing_token_command_reports_its_error() {
+    let mock = MockJira::start().await;
+
+    let error = profile(&mock, None, Some("echo 'vault is locked' >&2; exit 3"))
+        .connect(&RetryPolicy::default())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("vault is locked"), "{}", error);
+
+    let error = profile(&mock, None, None)
+        .connect(&RetryPolicy::default())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("no API token"), "{}", error);
+}
+
+#[tokio::test]
+async fn rejected_credentials_get_a_diagnostic() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/myself",
+        Reply::status(401, json!({ "errorMessages": ["Unauthorized"] })),
+    );
+
+    let site = profile(&mock, Some("wrong"), None)
+        .connect(&RetryPolicy::default())
+        .await
+        .unwrap();
+    let error = site.myself().await.unwrap_err();
+    let diagnostic = credentials_diagnostic(&site, &error);
+    assert!(diagnostic.contains(mock.url()), "{}", diagnostic);
+    assert!(diagnostic.contains("rejected"), "{}", diagnostic);
+}
@@ -0,0 +1,219 @@
+mod common;
+
+use common::{McpClient, MockJira, Reply, cloud, site};
+use jira_rs::jira::Jira;
+use jira_rs::site::Deployment;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn stub_issue(mock: &MockJira) {
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({
+            "key": "PROJ-1",
+            "fields": {
+                "summary": "Checkout flow",
+                "status": { "name": "In Progress" },
+                "issuetype": { "name": "Story" },
+                "subtasks": [{ "key": "PROJ-2" }, { "key": "PROJ-3" }]
+            }
+        })),
+    );
+    mock.on("DELETE", "/rest/api/3/issue/PROJ-1", Reply::no_content());
+}
+
+#[tokio::test]
+async fn issue_delete_dry_run_previews_without_deleting() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(&mock);
+
+    let out = client
+        .call_ok(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-1", "dryRun": true }),
+        )
+        .await;
+    assert_eq!(out["dryRun"], true);
+    let issue = &out["preview"]["issues"][0];
+    assert_eq!(issue["summary"], "Checkout flow");
+    assert_eq!(issue["status"], "In Progress");
+    assert_eq!(issue["subtaskCount"], 2);
+    assert_eq!(issue["subtasks"], json!(["PROJ-2", "PROJ-3"]));
+    assert!(out["preview"]["warning"].is_string());
+    assert!(
+        mock.requests_to("DELETE", "/rest/api/3/issue/PROJ-1")
+            .is_empty()
+    );
+
+    let request = mock.only_request("GET", "/rest/api/3/issue/PROJ-1");
+    assert_eq!(
+        request.query_param("fields").as_deref(),
+        Some("summary,status,issuetype,subtasks")
+    );
+}
+
+#[tokio::test]
+async fn confirm_token_executes_the_previewed_operation_once() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(&mock);
+
+    let out = client
+        .call_ok(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-1", "deleteSubtasks": true, "dryRun": true }),
+        )
+        .await;
+    let token = out["confirmToken"].as_str().unwrap().to_string();
+    assert!(out["preview"].get("warning").is_none());
+
+    // A token only confirms the exact call it was issued for
+    let error = client
+        .call_err(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-1", "confirmToken": token }),
+        )
+        .await;
+    assert_eq!(error["kind"], "validation");
+    assert!(
+        mock.requests_to("DELETE", "/rest/api/3/issue/PROJ-1")
+            .is_empty()
+    );
+
+    let out = client
+        .call_ok(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-1", "deleteSubtasks": true, "dryRun": true }),
+        )
+        .await;
+    let token = out["confirmToken"].as_str().unwrap().to_string();
+    let args = json!({ "issueKey": "PROJ-1", "deleteSubtasks": true, "confirmToken": token });
+    let out = client.call_ok("issue_delete", args.clone()).await;
+    assert_eq!(out["success"], true);
+    let request = mock.only_request("DELETE", "/rest/api/3/issue/PROJ-1");
+    assert_eq!(
+        request.query_param("deleteSubtasks").as_deref(),
+        Some("true")
+    );
+
+    // Tokens are single use
+    let error = client.call_err("issue_delete", args).await;
+    assert_eq!(error["kind"], "validation");
+    assert_eq!(
+        mock.requests_to("DELETE", "/rest/api/3/issue/PROJ-1").len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn enforced_confirmation_requires_a_token() {
+    let mock = MockJira::start().await;
+    stub_issue(&mock);
+    let mut sites = BTreeMap::new();
+    sites.insert("default".to_string(), site(&mock, Deployment::Cloud));
+    let jira = Jira::new(sites, "default").with_confirmation_required(true);
+    let mut client = McpClient::connect(jira).await;
+
+    let out = client
+        .call_ok(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-1", "deleteSubtasks": true }),
+        )
+        .await;
+    assert_eq!(out["dryRun"], true);
+    assert!(
+        mock.requests_to("DELETE", "/rest/api/3/issue/PROJ-1")
+            .is_empty()
+    );
+
+    let token = out["confirmToken"].as_str().unwrap();
+    client
+        .call_ok(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-1", "deleteSubtasks": true, "confirmToken": token }),
+        )
+        .await;
+    mock.only_request("DELETE", "/rest/api/3/issue/PROJ-1");
+}
+
+#[tokio::test]
+async fn issue_archive_dry_run_reports_each_issue() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(&mock);
+
+    let out = client
+        .call_ok(
+            "issue_archive",
+            json!({ "issueKeys": ["PROJ-1", "PROJ-404"], "dryRun": true }),
+        )
+        .await;
+    assert_eq!(out["preview"]["issues"][0]["key"], "PROJ-1");
+    assert!(out["preview"]["errors"]["PROJ-404"].is_string());
+    assert!(
+        mock.requests_to("PUT", "/rest/api/3/issue/archive")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn sprint_delete_dry_run_counts_sprint_issues() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/sprint/12",
+        Reply::json(json!({ "id": 12, "name": "Sprint 12", "state": "future" })),
+    );
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/sprint/12/issue",
+        Reply::json(json!({ "total": 17, "issues": [] })),
+    );
+
+    let out = client
+        .call_ok("sprint_delete", json!({ "sprintId": 12, "dryRun": true }))
+        .await;
+    assert_eq!(
+        out["preview"]["sprint"],
+        json!({ "id": 12, "name": "Sprint 12", "state": "future" })
+    );
+    assert_eq!(out["preview"]["issueCount"], 17);
+    assert!(
+        mock.requests_to("DELETE", "/rest/agile/1.0/sprint/12")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn issue_delete_link_dry_run_describes_the_link() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issueLink/100",
+        Reply::json(json!({
+            "id": "100",
+            "type": { "name": "Blocks" },
+            "inwardIssue": { "key": "PROJ-1", "fields": { "summary": "Checkout flow" } },
+            "outwardIssue": { "key": "PROJ-9", "fields": { "summary": "Payments API" } }
+        })),
+    );
+
+    let out = client
+        .call_ok(
+            "issue_delete_link",
+            json!({ "linkId": "100", "dryRun": true }),
+        )
+        .await;
+    assert_eq!(
+        out["preview"]["link"],
+        json!({
+            "id": "100",
+            "type": "Blocks",
+            "inwardIssue": { "key": "PROJ-1", "summary": "Checkout flow" },
+            "outwardIssue": { "key": "PROJ-9", "summary": "Payments API" }
+        })
+    );
+    assert!(
+        mock.requests_to("DELETE", "/rest/api/3/issueLink/100")
+            .is_empty()
+    );
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use common::{MockJira, Reply, site};
+use jira_rs::http::{HttpConfig, router};
+use jira_rs::jira::Jira;
+use jira_rs::site::Deployment;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use tokio_util::sync::CancellationToken;
+
+struct HttpServer {
+    url: String,
+    client: reqwest::Client,
+    shutdown: CancellationToken,
+}
+
+impl HttpServer {
+    async fn start(mock: &MockJira) -> Self {
+        let mut sites = BTreeMap::new();
+        sites.insert("default".to_string(), site(mock, Deployment::Cloud));
+        let jira = Jira::new(sites, "default");
+
+        let config = HttpConfig {
+            bind: HttpConfig::parse_bind("127.0.0.1:0").unwrap(),
+            session_idle_timeout: None,
+        };
+        let shutdown = CancellationToken::new();
+        let app = router(jira, &config, shutdown.clone());
+        let listener = tokio::net::TcpListener::bind(config.bind).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let signal = shutdown.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move { signal.cancelled().await })
+                .await
+                .unwrap();
+        });
+
+        Self {
+            url,
+            client: reqwest::Client::new(),
+            shutdown,
+        }
+    }
+
+    async fn post(&self, session: Option<&str>, message: Value) -> reqwest::Response {
+        let mut request = self
+            .client
+            .post(format!("{}/mcp", self.url))
+            .header("Accept", "application/json, text/event-stream")
+            .json(&message);
+        if let Some(session) = session {
+            request = request.header("Mcp-Session-Id", session);
+        }
+        request.send().await.unwrap()
+    }
+
+    /// Opens a session and returns its id.
+    async fn initialize(&self) -> String {
+        let response = self
+            .post(
+                None,
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": "2025-03-26",
+                        "capabilities": {},
+                        "clientInfo": { "name": "jira-rs-e2e", "version": "0.0.0" }
+                    }
+                }),
+            )
+            .await;
+        assert!(response.status().is_success(), "{}", response.status());
+        let session = response.headers()["mcp-session-id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let init = sse_message(response).await;
+        assert!(init.get("result").is_some(), "{}", init);
+
+        let ack = self
+            .post(
+                Some(&session),
+                json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            )
+            .await;
+        assert!(ack.status().is_success(), "{}", ack.status());
+        session
+    }
+
+    /// Calls `tool` in `session` and returns the JSON-RPC result.
+    async fn call(&self, session: &str, tool: &str, arguments: Value) -> Value {
+        let response = self
+            .post(
+                Some(session),
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": { "name": tool, "arguments": arguments }
+                }),
+            )
+            .await;
+        sse_message(response).await["result"].clone()
+    }
+
+    async fn health(&self) -> Value {
+        self.client
+            .get(format!("{}/health", self.url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap()
+    }
+}
+
+/// First JSON-RPC message carried by an SSE response.
+async fn sse_message(response: reqwest::Response) -> Value {
+    let body = response.text().await.unwrap();
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .find(|message| message.get("id").is_some())
+        .unwrap_or_else(|| panic!("no JSON-RPC message in {:?}", body))
+}
+
+#[tokio::test]
+async fn http_transport_serves_tools_per_session() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({ "id": "1", "key": "PROJ-1", "self": "", "fields": {} })),
+    );
+    let server = HttpServer::start(&mock).await;
+
+    let first = server.initialize().await;
+    let second = server.initialize().await;
+    assert_ne!(first, second);
+    assert_eq!(server.health().await["sessions"], 2);
+
+    let response = server
+        .post(
+            Some(&second),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": { "name": "issue_get", "arguments": { "issueKey": "PROJ-1" } }
+            }),
+        )
+        .await;
+    let message = sse_message(response).await;
+    let text = message["result"]["content"][0]["text"].as_str().unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(text).unwrap()["key"],
+        "PROJ-1"
+    );
+
+    // Closing a session leaves the others untouched
+    let closed = server
+        .client
+        .delete(format!("{}/mcp", server.url))
+        .header("Mcp-Session-Id", &first)
+        .send()
+        .await
+        .unwrap();
+    assert!(closed.status().is_success(), "{}", closed.status());
+    assert_eq!(server.health().await["sessions"], 1);
+
+    server.shutdown.cancel();
+}
+
+#[tokio::test]
+async fn http_transport_rejects_unknown_sessions() {
+    let mock = MockJira::start().await;
+    let server = HttpServer::start(&mock).await;
+
+    let response = server
+        .post(
+            Some("not-a-session"),
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }),
+        )
+        .await;
+    assert!(response.status().is_client_error(), "{}", response.status());
+
+    let health = server.health().await;
+    assert_eq!(health["status"], "ok");
+    assert_eq!(health["sessions"], 0);
+}
+
+#[tokio::test]
+async fn http_sessions_cannot_undo_each_others_operations() {
+    let mock = MockJira::start().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({ "id": "1", "key": "PROJ-1", "fields": { "summary": "Old" } })),
+    );
+    mock.on("PUT", "/rest/api/3/issue/PROJ-1", Reply::no_content());
+    let server = HttpServer::start(&mock).await;
+
+    let first = server.initialize().await;
+    let second = server.initialize().await;
+
+    let edit = server
+        .call(
+            &first,
+            "issue_edit_details",
+            json!({ "issueKey": "PROJ-1", "summary": "New" }),
+        )
+        .await;
+    assert_ne!(edit["isError"], true, "{}", edit);
+    let text = edit["content"][0]["text"].as_str().unwrap();
+    let undo_id = serde_json::from_str::<Value>(text).unwrap()["undoId"].clone();
+    assert_eq!(undo_id, 1);
+
+    for (tool, arguments) in [
+        ("undo_last", json!({})),
+        ("undo_operation", json!({ "operationId": undo_id })),
+    ] {
+        let result = server.call(&second, tool, arguments).await;
+        assert_eq!(result["isError"], true, "{}", result);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(text).unwrap()["error"]["kind"],
+            "not_found"
+        );
+    }
+    assert_eq!(mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1").len(), 1);
+
+    let undo = server.call(&first, "undo_last", json!({})).await;
+    assert_ne!(undo["isError"], true, "{}", undo);
+    assert_eq!(mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1").len(), 2);
+
+    server.shutdown.cancel();
+}
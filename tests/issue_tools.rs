@@ -0,0 +1,633 @@
+mod common;
+
+use common::{MockJira, Reply, adf_paragraph, cloud, createmeta, data_center, fields};
+use serde_json::json;
+
+fn stub_metadata(mock: &MockJira) {
+    mock.on("GET", "/rest/api/3/field", Reply::json(fields()));
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/createmeta",
+        Reply::json(createmeta()),
+    );
+}
+
+#[tokio::test]
+async fn issue_create_sends_adf_and_resolves_metadata() {
+    let (mock, mut client) = cloud().await;
+    stub_metadata(&mock);
+    mock.on(
+        "POST",
+        "/rest/api/3/issue",
+        Reply::status(
+            201,
+            json!({ "id": "10100", "key": "PROJ-7", "self": "http://jira/issue/10100" }),
+        ),
+    );
+
+    let out = client
+        .call_ok(
+            "issue_create",
+            json!({
+                "projectKey": "PROJ",
+                "issueType": "Story",
+                "summary": "Login page",
+                "description": "As a user I want to log in",
+                "priority": "High",
+                "labels": ["auth"],
+                "components": ["web"],
+                "storyPoints": 5,
+                "storyPointEstimate": 3
+            }),
+        )
+        .await;
+    assert_eq!(out["key"], "PROJ-7");
+
+    let meta = mock.only_request("GET", "/rest/api/3/issue/createmeta");
+    assert_eq!(meta.query_param("projectKeys").as_deref(), Some("PROJ"));
+
+    let body = mock.only_request("POST", "/rest/api/3/issue").body;
+    let fields = &body["fields"];
+    assert_eq!(fields["project"], json!({ "key": "PROJ" }));
+    // "Story" matches the untranslated name of the localized "Historia" type
+    assert_eq!(fields["issuetype"], json!({ "id": "10001" }));
+    assert_eq!(fields["summary"], "Login page");
+    assert_eq!(
+        fields["description"],
+        adf_paragraph("As a user I want to log in")
+    );
+    assert_eq!(fields["priority"], json!({ "name": "High" }));
+    assert_eq!(fields["labels"], json!(["auth"]));
+    assert_eq!(fields["components"], json!([{ "name": "web" }]));
+    assert_eq!(fields["customfield_10016"], json!(5.0));
+    assert_eq!(fields["customfield_10026"], json!(3.0));
+
+    // Both story point fields are resolved from a single /field download
+    assert_eq!(mock.requests_to("GET", "/rest/api/3/field").len(), 1);
+}
+
+#[tokio::test]
+async fn issue_create_reports_unknown_issue_type() {
+    let (mock, mut client) = cloud().await;
+    stub_metadata(&mock);
+
+    let error = client
+        .call_err(
+            "issue_create",
+            json!({ "projectKey": "PROJ", "issueType": "Initiative", "summary": "x" }),
+        )
+        .await;
+    assert_eq!(error["kind"], "not_found");
+    assert_eq!(error["name"], "Initiative");
+    assert!(mock.requests_to("POST", "/rest/api/3/issue").is_empty());
+}
+
+#[tokio::test]
+async fn metadata_is_cached_until_cache_refresh() {
+    let (mock, mut client) = cloud().await;
+    stub_metadata(&mock);
+    mock.on(
+        "POST",
+        "/rest/api/3/issue",
+        Reply::status(201, json!({ "id": "1", "key": "PROJ-1", "self": "" })),
+    );
+
+    let args =
+        json!({ "projectKey": "PROJ", "issueType": "Bug", "summary": "x", "storyPoints": 1 });
+    client.call_ok("issue_create", args.clone()).await;
+    client.call_ok("issue_create", args.clone()).await;
+    assert_eq!(mock.requests_to("GET", "/rest/api/3/field").len(), 1);
+    assert_eq!(
+        mock.requests_to("GET", "/rest/api/3/issue/createmeta")
+            .len(),
+        1
+    );
+
+    let cleared = client
+        .call_ok("cache_refresh", json!({ "scope": "fields" }))
+        .await;
+    assert_eq!(cleared["cleared"], json!({ "fields": 1 }));
+
+    client.call_ok("issue_create", args).await;
+    assert_eq!(mock.requests_to("GET", "/rest/api/3/field").len(), 2);
+    assert_eq!(
+        mock.requests_to("GET", "/rest/api/3/issue/createmeta")
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn issue_update_status_picks_matching_transition() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1/transitions",
+        Reply::json(json!({
+            "transitions": [
+                { "id": "11", "name": "Start work", "to": { "name": "En curso", "statusCategory": { "key": "indeterminate" } } },
+                { "id": "31", "name": "Finish", "to": { "name": "Done", "statusCategory": { "key": "done" } } }
+            ]
+        })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/transitions",
+        Reply::no_content(),
+    );
+
+    client
+        .call_ok(
+            "issue_update_status",
+            json!({ "issueKey": "PROJ-1", "status": "Done" }),
+        )
+        .await;
+    let body = mock
+        .only_request("POST", "/rest/api/3/issue/PROJ-1/transitions")
+        .body;
+    assert_eq!(body, json!({ "transition": { "id": "31" } }));
+
+    // Falls back to the status category when no name matches
+    client
+        .call_ok(
+            "issue_update_status",
+            json!({ "issueKey": "PROJ-1", "status": "In Progress" }),
+        )
+        .await;
+    let posted = mock.requests_to("POST", "/rest/api/3/issue/PROJ-1/transitions");
+    assert_eq!(posted[1].body, json!({ "transition": { "id": "11" } }));
+}
+
+#[tokio::test]
+async fn issue_assign_resolves_me_and_unassigned() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/myself",
+        Reply::json(json!({ "accountId": "acc-42" })),
+    );
+    mock.on(
+        "PUT",
+        "/rest/api/3/issue/PROJ-1/assignee",
+        Reply::no_content(),
+    );
+
+    client
+        .call_ok(
+            "issue_assign",
+            json!({ "issueKey": "PROJ-1", "assignee": "me" }),
+        )
+        .await;
+    client
+        .call_ok(
+            "issue_assign",
+            json!({ "issueKey": "PROJ-1", "assignee": "unassigned" }),
+        )
+        .await;
+
+    let puts = mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1/assignee");
+    assert_eq!(puts[0].body, json!({ "accountId": "acc-42" }));
+    assert_eq!(puts[1].body, json!({ "accountId": null }));
+}
+
+#[tokio::test]
+async fn issue_edit_details_only_sends_given_fields() {
+    let (mock, mut client) = cloud().await;
+    mock.on("PUT", "/rest/api/3/issue/PROJ-1", Reply::no_content());
+
+    client
+        .call_ok(
+            "issue_edit_details",
+            json!({ "issueKey": "PROJ-1", "summary": "New title", "description": "Details" }),
+        )
+        .await;
+    let body = mock.only_request("PUT", "/rest/api/3/issue/PROJ-1").body;
+    assert_eq!(
+        body,
+        json!({ "fields": { "summary": "New title", "description": adf_paragraph("Details") } })
+    );
+}
+
+#[tokio::test]
+async fn rich_text_inputs_are_converted_from_markdown() {
+    let (mock, mut client) = cloud().await;
+    mock.on("PUT", "/rest/api/3/issue/PROJ-1", Reply::no_content());
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/comment",
+        Reply::status(201, json!({ "id": "100" })),
+    );
+
+    client
+        .call_ok(
+            "issue_edit_details",
+            json!({ "issueKey": "PROJ-1", "description": "## Steps\n\n- Open **login**\n- Submit" }),
+        )
+        .await;
+    let body = mock.only_request("PUT", "/rest/api/3/issue/PROJ-1").body;
+    let description = &body["fields"]["description"];
+    assert_eq!(description["content"][0]["type"], "heading");
+    assert_eq!(description["content"][1]["type"], "bulletList");
+    assert_eq!(
+        description["content"][1]["content"][0]["content"][0]["content"][1],
+        json!({ "type": "text", "text": "login", "marks": [{ "type": "strong" }] })
+    );
+
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "```sh\nmake test\n```" }),
+        )
+        .await;
+    let body = mock
+        .only_request("POST", "/rest/api/3/issue/PROJ-1/comment")
+        .body;
+    assert_eq!(
+        body["body"]["content"][0],
+        json!({
+            "type": "codeBlock",
+            "attrs": { "language": "sh" },
+            "content": [{ "type": "text", "text": "make test" }]
+        })
+    );
+}
+
+#[tokio::test]
+async fn issue_set_story_points_uses_editable_field() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1/editmeta",
+        Reply::json(
+            json!({ "fields": { "customfield_10026": { "name": "Story point estimate" } } }),
+        ),
+    );
+    mock.on("PUT", "/rest/api/3/issue/PROJ-1", Reply::no_content());
+
+    client
+        .call_ok(
+            "issue_set_story_points",
+            json!({ "issueKey": "PROJ-1", "storyPoints": 8 }),
+        )
+        .await;
+    let body = mock.only_request("PUT", "/rest/api/3/issue/PROJ-1").body;
+    assert_eq!(body, json!({ "fields": { "customfield_10026": 8.0 } }));
+}
+
+#[tokio::test]
+async fn issue_set_story_points_reports_missing_field() {
+    let (mock, mut client) = cloud().await;
+    mock.on("GET", "/rest/api/3/field", Reply::json(fields()));
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1/editmeta",
+        Reply::json(json!({ "fields": {} })),
+    );
+
+    let error = client
+        .call_err(
+            "issue_set_story_points",
+            json!({ "issueKey": "PROJ-1", "storyPoints": 8 }),
+        )
+        .await;
+    assert_eq!(error["kind"], "not_found");
+    assert!(
+        error["hint"]
+            .as_str()
+            .unwrap()
+            .contains("customfield_10016")
+    );
+    assert!(
+        mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn issue_add_comment_and_log_work_send_adf() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/comment",
+        Reply::status(201, json!({ "id": "500" })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/worklog",
+        Reply::status(201, json!({ "id": "600" })),
+    );
+
+    let comment = client
+        .call_ok(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "Looks good" }),
+        )
+        .await;
+    assert_eq!(comment["id"], "500");
+    let body = mock
+        .only_request("POST", "/rest/api/3/issue/PROJ-1/comment")
+        .body;
+    assert_eq!(body, json!({ "body": adf_paragraph("Looks good") }));
+
+    client
+        .call_ok(
+            "issue_log_work",
+            json!({ "issueKey": "PROJ-1", "timeSpent": "1h 30m", "comment": "Pairing" }),
+        )
+        .await;
+    let body = mock
+        .only_request("POST", "/rest/api/3/issue/PROJ-1/worklog")
+        .body;
+    assert_eq!(
+        body,
+        json!({ "timeSpent": "1h 30m", "comment": adf_paragraph("Pairing") })
+    );
+}
+
+#[tokio::test]
+async fn issue_link_sends_link_type_and_direction() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/issueLink",
+        Reply::status(201, json!(null)),
+    );
+
+    client
+        .call_ok(
+            "issue_link",
+            json!({ "sourceIssueKey": "PROJ-1", "targetIssueKey": "PROJ-2", "linkType": "Is blocked by" }),
+        )
+        .await;
+    let body = mock.only_request("POST", "/rest/api/3/issueLink").body;
+    assert_eq!(
+        body,
+        json!({
+            "type": { "name": "Is blocked by" },
+            "inwardIssue": { "key": "PROJ-1" },
+            "outwardIssue": { "key": "PROJ-2" }
+        })
+    );
+}
+
+#[tokio::test]
+async fn issue_set_parent_uses_parent_field() {
+    let (mock, mut client) = cloud().await;
+    mock.on("PUT", "/rest/api/3/issue/PROJ-2", Reply::no_content());
+
+    let out = client
+        .call_ok(
+            "issue_set_parent",
+            json!({ "issueKey": "PROJ-2", "parentKey": "PROJ-1" }),
+        )
+        .await;
+    assert_eq!(out["message"], "Issue PROJ-2 linked to parent PROJ-1");
+    let body = mock.only_request("PUT", "/rest/api/3/issue/PROJ-2").body;
+    assert_eq!(body, json!({ "fields": { "parent": { "key": "PROJ-1" } } }));
+}
+
+#[tokio::test]
+async fn issue_set_parent_falls_back_to_epic_link() {
+    let (mock, mut client) = cloud().await;
+    mock.on("GET", "/rest/api/3/field", Reply::json(fields()));
+    mock.on_fn("PUT", "/rest/api/3/issue/PROJ-2", |req| {
+        if req.body["fields"].get("parent").is_some() {
+            Reply::status(
+                400,
+                json!({ "errorMessages": [], "errors": { "parent": "Field 'parent' cannot be set. It is not on the appropriate screen, or unknown." } }),
+            )
+        } else {
+            Reply::no_content()
+        }
+    });
+
+    let out = client
+        .call_ok(
+            "issue_set_parent",
+            json!({ "issueKey": "PROJ-2", "parentKey": "PROJ-1" }),
+        )
+        .await;
+    assert_eq!(
+        out["message"],
+        "Issue PROJ-2 linked to Epic PROJ-1 (legacy field)"
+    );
+
+    let puts = mock.requests_to("PUT", "/rest/api/3/issue/PROJ-2");
+    assert_eq!(puts.len(), 2);
+    assert_eq!(
+        puts[1].body,
+        json!({ "fields": { "customfield_10014": "PROJ-1" } })
+    );
+}
+
+#[tokio::test]
+async fn issue_set_parent_does_not_fall_back_on_unrelated_errors() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "PUT",
+        "/rest/api/3/issue/PROJ-2",
+        Reply::status(
+            403,
+            json!({ "errorMessages": ["You do not have permission to edit issues"] }),
+        ),
+    );
+
+    let error = client
+        .call_err(
+            "issue_set_parent",
+            json!({ "issueKey": "PROJ-2", "parentKey": "PROJ-1" }),
+        )
+        .await;
+    assert_eq!(error["kind"], "http");
+    assert_eq!(error["status"], 403);
+    assert!(mock.requests_to("GET", "/rest/api/3/field").is_empty());
+    assert_eq!(mock.requests_to("PUT", "/rest/api/3/issue/PROJ-2").len(), 1);
+}
+
+#[tokio::test]
+async fn issue_get_expands_filter_preset() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(
+            json!({ "id": "1", "key": "PROJ-1", "self": "", "fields": { "summary": "Hello" } }),
+        ),
+    );
+
+    let issue = client
+        .call_ok(
+            "issue_get",
+            json!({ "issueKey": "PROJ-1", "filter": "id key summary" }),
+        )
+        .await;
+    assert_eq!(issue["fields"]["summary"], "Hello");
+    let request = mock.only_request("GET", "/rest/api/3/issue/PROJ-1");
+    assert_eq!(
+        request.query_param("fields").as_deref(),
+        Some("id,key,summary")
+    );
+}
+
+#[tokio::test]
+async fn issue_get_renders_rich_text_as_markdown() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({
+            "id": "1",
+            "key": "PROJ-1",
+            "fields": {
+                "summary": "Hello",
+                "description": adf_paragraph("As a user I want to log in"),
+                "comment": { "comments": [{ "id": "100", "body": adf_paragraph("Done") }], "total": 1 }
+            }
+        })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({
+            "issues": [{ "key": "PROJ-1", "fields": { "description": adf_paragraph("Found") } }],
+            "isLast": true
+        })),
+    );
+
+    let issue = client
+        .call_ok("issue_get", json!({ "issueKey": "PROJ-1" }))
+        .await;
+    assert_eq!(issue["fields"]["description"], "As a user I want to log in");
+    assert_eq!(issue["fields"]["comment"]["comments"][0]["body"], "Done");
+    assert_eq!(issue["fields"]["summary"], "Hello");
+
+    let issue = client
+        .call_ok(
+            "issue_get",
+            json!({ "issueKey": "PROJ-1", "format": "adf" }),
+        )
+        .await;
+    assert_eq!(
+        issue["fields"]["description"],
+        adf_paragraph("As a user I want to log in")
+    );
+
+    let page = client
+        .call_ok("search_issues", json!({ "jql": "project = PROJ" }))
+        .await;
+    assert_eq!(page["issues"][0]["fields"]["description"], "Found");
+    let page = client
+        .call_ok(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "format": "adf" }),
+        )
+        .await;
+    assert_eq!(
+        page["issues"][0]["fields"]["description"],
+        adf_paragraph("Found")
+    );
+}
+
+#[tokio::test]
+async fn issue_delete_surfaces_jira_errors() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "DELETE",
+        "/rest/api/3/issue/PROJ-9",
+        Reply::status(404, json!({ "errorMessages": ["Issue does not exist or you do not have permission to see it."] })),
+    );
+
+    let error = client
+        .call_err(
+            "issue_delete",
+            json!({ "issueKey": "PROJ-9", "deleteSubtasks": true }),
+        )
+        .await;
+    assert_eq!(error["status"], 404);
+    assert_eq!(
+        error["errorMessages"],
+        json!(["Issue does not exist or you do not have permission to see it."])
+    );
+    let request = mock.only_request("DELETE", "/rest/api/3/issue/PROJ-9");
+    assert_eq!(
+        request.query_param("deleteSubtasks").as_deref(),
+        Some("true")
+    );
+}
+
+#[tokio::test]
+async fn issue_archive_uses_bulk_endpoint_on_cloud() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "PUT",
+        "/rest/api/3/issue/archive",
+        Reply::json(json!({ "numberOfIssuesUpdated": 2 })),
+    );
+
+    let out = client
+        .call_ok(
+            "issue_archive",
+            json!({ "issueKeys": ["PROJ-1", "PROJ-2"] }),
+        )
+        .await;
+    assert_eq!(out["numberOfIssuesUpdated"], 2);
+    let body = mock.only_request("PUT", "/rest/api/3/issue/archive").body;
+    assert_eq!(body, json!({ "issueIdsOrKeys": ["PROJ-1", "PROJ-2"] }));
+}
+
+#[tokio::test]
+async fn data_center_uses_v2_plain_text_and_usernames() {
+    let (mock, mut client) = data_center().await;
+    mock.on(
+        "POST",
+        "/rest/api/2/issue/PROJ-1/comment",
+        Reply::status(201, json!({ "id": "1" })),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/2/myself",
+        Reply::json(json!({ "name": "jdoe" })),
+    );
+    mock.on(
+        "PUT",
+        "/rest/api/2/issue/PROJ-1/assignee",
+        Reply::no_content(),
+    );
+    mock.on(
+        "PUT",
+        "/rest/api/2/issue/PROJ-1/archive",
+        Reply::no_content(),
+    );
+
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "Plain" }),
+        )
+        .await;
+    let body = mock
+        .only_request("POST", "/rest/api/2/issue/PROJ-1/comment")
+        .body;
+    assert_eq!(body, json!({ "body": "Plain" }));
+
+    client
+        .call_ok(
+            "issue_assign",
+            json!({ "issueKey": "PROJ-1", "assignee": "me" }),
+        )
+        .await;
+    let body = mock
+        .only_request("PUT", "/rest/api/2/issue/PROJ-1/assignee")
+        .body;
+    assert_eq!(body, json!({ "name": "jdoe" }));
+
+    let out = client
+        .call_ok(
+            "issue_archive",
+            json!({ "issueKeys": ["PROJ-1", "PROJ-404"] }),
+        )
+        .await;
+    assert_eq!(out["numberOfIssuesUpdated"], 1);
+    assert_eq!(out["updated"], json!(["PROJ-1"]));
+    assert!(out["errors"].get("PROJ-404").is_some());
+}
@@ -0,0 +1,276 @@
+use jira_rs::domains::helpers::{adf_to_markdown, markdown_to_adf};
+use serde_json::{Value, json};
+
+fn doc(content: Value) -> Value {
+    json!({ "version": 1, "type": "doc", "content": content })
+}
+
+fn text(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+fn paragraph(content: Value) -> Value {
+    json!({ "type": "paragraph", "content": content })
+}
+
+#[test]
+fn renders_text_blocks_and_marks() {
+    let adf = doc(json!([
+        { "type": "heading", "attrs": { "level": 2 }, "content": [text("Context")] },
+        paragraph(json!([
+            text("Log in with "),
+            { "type": "text", "text": "SSO ", "marks": [{ "type": "strong" }] },
+            { "type": "text", "text": "only", "marks": [{ "type": "em" }] },
+            text(", see "),
+            {
+                "type": "text",
+                "text": "the spec",
+                "marks": [{ "type": "link", "attrs": { "href": "https://example.com/spec" } }]
+            },
+            text(" or run "),
+            { "type": "text", "text": "make login", "marks": [{ "type": "code" }] },
+            { "type": "hardBreak" },
+            { "type": "mention", "attrs": { "id": "acc-1", "text": "@Ana" } },
+            text(" "),
+            { "type": "emoji", "attrs": { "shortName": ":smile:", "text": "😄" } },
+            text(" "),
+            { "type": "status", "attrs": { "text": "In review" } },
+            text(" "),
+            { "type": "date", "attrs": { "timestamp": "1767225600000" } }
+        ])),
+        { "type": "rule" },
+        {
+            "type": "codeBlock",
+            "attrs": { "language": "rust" },
+            "content": [text("fn main() {}")]
+        },
+        { "type": "blockquote", "content": [paragraph(json!([text("Quoted")]))] },
+        {
+            "type": "panel",
+            "attrs": { "panelType": "warning" },
+            "content": [paragraph(json!([text("Careful")]))]
+        }
+    ]));
+
+    assert_eq!(
+        adf_to_markdown(&adf),
+        "## Context\n\n\
+         Log in with **SSO** *only*, see [the spec](https://example.com/spec) or run `make login`\n\
+         @Ana 😄 [IN REVIEW] 2026-01-01\n\n\
+         ---\n\n\
+         ```rust\nfn main() {}\n```\n\n\
+         > Quoted\n\n\
+         > **Warning:** Careful"
+    );
+}
+
+#[test]
+fn renders_lists_tables_and_media() {
+    let item = |content: Value| json!({ "type": "listItem", "content": content });
+    let cell = |kind: &str, value: &str| json!({ "type": kind, "content": [paragraph(json!([text(value)]))] });
+    let adf = doc(json!([
+        {
+            "type": "bulletList",
+            "content": [
+                item(json!([
+                    paragraph(json!([text("Backend")])),
+                    {
+                        "type": "orderedList",
+                        "attrs": { "order": 3 },
+                        "content": [
+                            item(json!([paragraph(json!([text("API")]))])),
+                            item(json!([paragraph(json!([text("DB")]))]))
+                        ]
+                    }
+                ])),
+                item(json!([paragraph(json!([text("Frontend")]))]))
+            ]
+        },
+        {
+            "type": "taskList",
+            "content": [
+                { "type": "taskItem", "attrs": { "state": "DONE" }, "content": [text("Design")] },
+                { "type": "taskItem", "attrs": { "state": "TODO" }, "content": [text("Build")] }
+            ]
+        },
+        {
+            "type": "table",
+            "content": [
+                { "type": "tableRow", "content": [cell("tableHeader", "Env"), cell("tableHeader", "URL")] },
+                { "type": "tableRow", "content": [cell("tableCell", "prod"), cell("tableCell", "a|b")] }
+            ]
+        },
+        {
+            "type": "mediaSingle",
+            "content": [{ "type": "media", "attrs": { "id": "uuid-1", "type": "file", "alt": "screen.png" } }]
+        },
+        {
+            "type": "expand",
+            "attrs": { "title": "Logs" },
+            "content": [paragraph(json!([text("trace")]))]
+        }
+    ]));
+
+    assert_eq!(
+        adf_to_markdown(&adf),
+        "- Backend\n  3. API\n  4. DB\n- Frontend\n\n\
+         - [x] Design\n- [ ] Build\n\n\
+         | Env | URL |\n| --- | --- |\n| prod | a\\|b |\n\n\
+         [media: screen.png]\n\n\
+         **Logs**\n\ntrace"
+    );
+}
+
+#[test]
+fn converts_markdown_blocks() {
+    let adf = markdown_to_adf(
+        "# Plan\n\
+         \n\
+         Steps:\n\
+         next line\n\
+         \n\
+         1. Backend\n\
+         \x20  - API\n\
+         \x20  - DB\n\
+         2. Frontend\n\
+         \n\
+         - [x] Design\n\
+         - [ ] Build\n\
+         \n\
+         ```rust\n\
+         fn main() {}\n\
+         ```\n\
+         \n\
+         > Quoted\n\
+         \n\
+         | Env | URL |\n\
+         |-----|:---:|\n\
+         | prod | a\\|b |\n\
+         \n\
+         ---",
+    );
+
+    let p = |content: Value| paragraph(content);
+    let item = |content: Value| json!({ "type": "listItem", "content": content });
+    let cell =
+        |kind: &str, value: &str| json!({ "type": kind, "content": [p(json!([text(value)]))] });
+    assert_eq!(
+        adf.0,
+        doc(json!([
+            { "type": "heading", "attrs": { "level": 1 }, "content": [text("Plan")] },
+            p(json!([text("Steps:"), { "type": "hardBreak" }, text("next line")])),
+            {
+                "type": "orderedList",
+                "content": [
+                    item(json!([
+                        p(json!([text("Backend")])),
+                        {
+                            "type": "bulletList",
+                            "content": [
+                                item(json!([p(json!([text("API")]))])),
+                                item(json!([p(json!([text("DB")]))]))
+                            ]
+                        }
+                    ])),
+                    item(json!([p(json!([text("Frontend")]))]))
+                ]
+            },
+            {
+                "type": "taskList",
+                "attrs": { "localId": "tasks" },
+                "content": [
+                    { "type": "taskItem", "attrs": { "localId": "task-1", "state": "DONE" }, "content": [text("Design")] },
+                    { "type": "taskItem", "attrs": { "localId": "task-2", "state": "TODO" }, "content": [text("Build")] }
+                ]
+            },
+            { "type": "codeBlock", "attrs": { "language": "rust" }, "content": [text("fn main() {}")] },
+            { "type": "blockquote", "content": [p(json!([text("Quoted")]))] },
+            {
+                "type": "table",
+                "content": [
+                    { "type": "tableRow", "content": [cell("tableHeader", "Env"), cell("tableHeader", "URL")] },
+                    { "type": "tableRow", "content": [cell("tableCell", "prod"), cell("tableCell", "a|b")] }
+                ]
+            },
+            { "type": "rule" }
+        ]))
+    );
+}
+
+#[test]
+fn converts_inline_marks_and_links() {
+    let adf = markdown_to_adf(
+        "Use **bold _and italic_**, ~~old~~, `snake_case` and [the *spec*](https://example.com/spec \"Spec\"), \
+         see <https://example.com/a>; keep my_var_name and 2 * 3 * 4 as text, \\*not italic\\*",
+    );
+    let mark = |kind: &str| json!({ "type": kind });
+    let link = json!({ "type": "link", "attrs": { "href": "https://example.com/spec" } });
+    assert_eq!(
+        adf.0["content"][0]["content"],
+        json!([
+            text("Use "),
+            { "type": "text", "text": "bold ", "marks": [mark("strong")] },
+            { "type": "text", "text": "and italic", "marks": [mark("strong"), mark("em")] },
+            text(", "),
+            { "type": "text", "text": "old", "marks": [mark("strike")] },
+            text(", "),
+            { "type": "text", "text": "snake_case", "marks": [mark("code")] },
+            text(" and "),
+            { "type": "text", "text": "the ", "marks": [link] },
+            { "type": "text", "text": "spec", "marks": [link, mark("em")] },
+            text(", see "),
+            { "type": "inlineCard", "attrs": { "url": "https://example.com/a" } },
+            text("; keep my_var_name and 2 * 3 * 4 as text, *not italic*")
+        ])
+    );
+
+    // Plain text stays a single paragraph and empty text an empty document
+    assert_eq!(
+        markdown_to_adf("Just text").0,
+        doc(json!([p_text("Just text")]))
+    );
+    assert_eq!(markdown_to_adf("").0, doc(json!([])));
+}
+
+#[test]
+fn markdown_round_trips_through_adf() {
+    let markdown = "## Context\n\n\
+                    Log in with **SSO** *only*, see [the spec](https://example.com/spec) or run `make login`\n\
+                    second line\n\n\
+                    - Backend\n  1. API\n  2. DB\n- Frontend\n\n\
+                    - [x] Design\n- [ ] Build\n\n\
+                    ```rust\nfn main() {}\n```\n\n\
+                    > Quoted\n\n\
+                    | Env | URL |\n| --- | --- |\n| prod | a\\|b |\n\n\
+                    ---";
+    assert_eq!(adf_to_markdown(&markdown_to_adf(markdown).0), markdown);
+}
+
+#[test]
+fn unicode_whitespace_in_indentation_is_kept_as_text() {
+    // Non-breaking and ideographic spaces are not indentation; paragraph
+    // lines are trimmed as usual
+    let list = markdown_to_adf("- a\n \u{00A0}b").0;
+    assert_eq!(
+        list["content"][0]["content"][0]["content"][0]["content"],
+        json!([
+            text("a"),
+            { "type": "hardBreak" },
+            text("b")
+        ])
+    );
+    let ordered = markdown_to_adf("1. a\n \u{3000}b").0;
+    assert_eq!(ordered["content"][0]["type"], "orderedList");
+    assert_eq!(
+        ordered["content"][0]["content"][0]["content"][0]["content"][2],
+        text("b")
+    );
+    assert_eq!(
+        markdown_to_adf("  ```\n\u{3000}x\n```").0,
+        doc(json!([{ "type": "codeBlock", "content": [text("\u{3000}x")] }]))
+    );
+}
+
+fn p_text(value: &str) -> Value {
+    paragraph(json!([text(value)]))
+}
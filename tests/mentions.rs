@@ -0,0 +1,158 @@
+mod common;
+
+use common::{MockJira, Reply, cloud};
+use serde_json::{Value, json};
+
+fn user(account_id: &str, name: &str, email: &str) -> Value {
+    json!({
+        "accountId": account_id,
+        "accountType": "atlassian",
+        "displayName": name,
+        "emailAddress": email,
+        "active": true
+    })
+}
+
+fn stub_users(mock: &MockJira) {
+    mock.on_fn("GET", "/rest/api/3/user/search", |request| {
+        let query = request.query_param("query").unwrap_or_default();
+        Reply::json(match query.as_str() {
+            "ana" | "Ana" => json!([
+                user("acc-1", "Ana Lopez", "ana.lopez@example.com"),
+                user("acc-2", "Ana Perez", "ana.perez@example.com")
+            ]),
+            "bob@example.com" => json!([user("acc-3", "Bob Stone", "bob@example.com")]),
+            "İrem" => json!([user("acc-5", "İrem Yılmaz", "irem@example.com")]),
+            "carol" => json!([
+                user("acc-4", "Carol Diaz", "carol@example.com"),
+                { "accountId": "app-1", "accountType": "app", "displayName": "Carol Bot" }
+            ]),
+            _ => json!([]),
+        })
+    });
+    mock.on(
+        "GET",
+        "/rest/api/3/user",
+        Reply::json(user("acc-9", "Dana Kim", "dana@example.com")),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/comment",
+        Reply::status(201, json!({ "id": "100" })),
+    );
+}
+
+fn mention(id: &str, text: &str) -> Value {
+    json!({ "type": "mention", "attrs": { "id": id, "text": text } })
+}
+
+fn text(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+#[tokio::test]
+async fn comment_mentions_become_mention_nodes() {
+    let (mock, mut client) = cloud().await;
+    stub_users(&mock);
+
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({
+                "issueKey": "PROJ-1",
+                "comment": "@Ana Lopez and @carol please review, cc @bob@example.com and [~accountId:acc-9]. Thanks @here"
+            }),
+        )
+        .await;
+
+    let body = mock
+        .only_request("POST", "/rest/api/3/issue/PROJ-1/comment")
+        .body;
+    assert_eq!(
+        body["body"]["content"][0]["content"],
+        json!([
+            mention("acc-1", "@Ana Lopez"),
+            text(" and "),
+            mention("acc-4", "@Carol Diaz"),
+            text(" please review, cc "),
+            mention("acc-3", "@Bob Stone"),
+            text(" and "),
+            mention("acc-9", "@Dana Kim"),
+            text(". Thanks @here")
+        ])
+    );
+    let lookup = mock.only_request("GET", "/rest/api/3/user");
+    assert_eq!(lookup.query_param("accountId").as_deref(), Some("acc-9"));
+}
+
+#[tokio::test]
+async fn ambiguous_mentions_are_rejected() {
+    let (mock, mut client) = cloud().await;
+    stub_users(&mock);
+
+    let error = client
+        .call_err(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "@ana please review" }),
+        )
+        .await;
+    assert_eq!(error["kind"], "validation");
+    let message = error["message"].as_str().unwrap();
+    assert!(
+        message.contains("'@ana' matches several users"),
+        "{}",
+        message
+    );
+    assert!(message.contains("Ana Lopez <ana.lopez@example.com> (accountId acc-1)"));
+    assert!(message.contains("Ana Perez"));
+    assert!(
+        mock.requests_to("POST", "/rest/api/3/issue/PROJ-1/comment")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn mentions_in_code_and_links_are_left_alone() {
+    let (mock, mut client) = cloud().await;
+    stub_users(&mock);
+
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({
+                "issueKey": "PROJ-1",
+                "comment": "Run `@ana` or see [@ana](https://example.com)\n\n```\n@ana\n```\n\nmail me at me@example.com"
+            }),
+        )
+        .await;
+    assert!(
+        mock.requests_to("GET", "/rest/api/3/user/search")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn non_ascii_names_are_matched_in_place() {
+    let (mock, mut client) = cloud().await;
+    stub_users(&mock);
+
+    // 'İ' lowercases to two chars, which must not shift the mention
+    client
+        .call_ok(
+            "issue_add_comment",
+            json!({ "issueKey": "PROJ-1", "comment": "Thanks @İrem Yılmaz, merged" }),
+        )
+        .await;
+
+    let body = mock
+        .only_request("POST", "/rest/api/3/issue/PROJ-1/comment")
+        .body;
+    assert_eq!(
+        body["body"]["content"][0]["content"],
+        json!([
+            text("Thanks "),
+            mention("acc-5", "@İrem Yılmaz"),
+            text(", merged")
+        ])
+    );
+}
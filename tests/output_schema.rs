@@ -0,0 +1,98 @@
+mod common;
+
+use common::{Reply, cloud};
+use serde_json::{Value, json};
+
+fn tool<'a>(tools: &'a [Value], name: &str) -> &'a Value {
+    tools
+        .iter()
+        .find(|t| t["name"] == name)
+        .unwrap_or_else(|| panic!("missing tool {}", name))
+}
+
+#[tokio::test]
+async fn every_tool_advertises_an_object_output_schema() {
+    let (_mock, mut client) = cloud().await;
+
+    let response = client.request("tools/list", json!({})).await;
+    let tools = response["result"]["tools"].as_array().unwrap();
+    for tool in tools {
+        let schema = &tool["outputSchema"];
+        assert_eq!(schema["type"], "object", "{}", tool["name"]);
+        assert!(schema.get("$schema").is_none(), "{}", tool["name"]);
+    }
+
+    let created = &tool(tools, "issue_create")["outputSchema"];
+    assert_eq!(created["required"], json!(["id", "key", "self"]));
+
+    // Destructive tools describe both the result and the dry-run preview
+    let properties = &tool(tools, "issue_delete")["outputSchema"]["properties"];
+    for field in ["success", "message", "dryRun", "preview", "confirmToken"] {
+        assert!(properties.get(field).is_some(), "missing {}", field);
+    }
+}
+
+#[tokio::test]
+async fn results_carry_structured_content() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issuetype",
+        Reply::json(
+            json!([{ "id": "10001", "name": "Story", "description": "", "subtask": false }]),
+        ),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/comment",
+        Reply::status(
+            201,
+            json!({
+                "id": "100",
+                "self": "https://example.atlassian.net/rest/api/3/issue/1/comment/100",
+                "created": "2026-01-05T09:00:00.000+0000",
+                "jsdPublic": true
+            }),
+        ),
+    );
+
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "list_issue_types", "arguments": {} }),
+        )
+        .await;
+    let result = &response["result"];
+    assert_eq!(
+        result["structuredContent"],
+        json!({
+            "issueTypes": [{ "id": "10001", "name": "Story", "description": "", "subtask": false }]
+        })
+    );
+    let text: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(text, result["structuredContent"]);
+
+    // Attributes outside the declared ones are passed through
+    let response = client
+        .request(
+            "tools/call",
+            json!({
+                "name": "issue_add_comment",
+                "arguments": { "issueKey": "PROJ-1", "comment": "Hi" }
+            }),
+        )
+        .await;
+    let comment = &response["result"]["structuredContent"];
+    assert_eq!(comment["id"], "100");
+    assert_eq!(comment["jsdPublic"], true);
+
+    // Errors stay unstructured
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "issue_get", "arguments": { "issueKey": "PROJ-404" } }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], true);
+    assert!(response["result"].get("structuredContent").is_none());
+}
@@ -0,0 +1,256 @@
+mod common;
+
+use common::{McpClient, MockJira, Reply, cloud, site};
+use jira_rs::jira::Jira;
+use jira_rs::policy::ToolPolicy;
+use jira_rs::site::Deployment;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+async fn get(client: &mut McpClient, name: &str, arguments: Value) -> Vec<String> {
+    let response = client
+        .request(
+            "prompts/get",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await;
+    response["result"]["messages"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{}", response))
+        .iter()
+        .map(|m| {
+            assert_eq!(m["role"], "user");
+            m["content"]["text"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn prompts_are_listed_with_their_arguments() {
+    let (_mock, mut client) = cloud().await;
+
+    let response = client.request("prompts/list", json!({})).await;
+    let prompts = response["result"]["prompts"].as_array().unwrap();
+    let names: Vec<_> = prompts
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "groom_backlog",
+            "triage_bugs",
+            "sprint_review",
+            "break_epic"
+        ]
+    );
+    assert_eq!(
+        prompts[1]["arguments"][0],
+        json!({ "name": "project", "description": "Project key.", "required": true })
+    );
+}
+
+#[tokio::test]
+async fn groom_backlog_embeds_the_backlog() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board",
+        Reply::json(json!({ "values": [{ "id": 7, "name": "Team board" }] })),
+    );
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board/7/backlog",
+        Reply::json(json!({
+            "issues": [{
+                "id": "1",
+                "key": "PROJ-5",
+                "fields": { "summary": "Export to CSV", "labels": ["api"] }
+            }]
+        })),
+    );
+
+    let messages = get(
+        &mut client,
+        "groom_backlog",
+        json!({ "board": "Team board" }),
+    )
+    .await;
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].starts_with("Groom the backlog of board 'Team board'"));
+    assert!(messages[1].starts_with("Backlog (1 issues)"));
+    assert!(messages[1].contains("Export to CSV"));
+    assert!(messages[1].contains("\"api\""));
+    let board = mock.only_request("GET", "/rest/agile/1.0/board");
+    assert_eq!(board.query_param("name").as_deref(), Some("Team board"));
+    let backlog = mock.only_request("GET", "/rest/agile/1.0/board/7/backlog");
+    assert_eq!(
+        backlog.query_param("fields").as_deref(),
+        Some("summary,status,assignee,priority,created,updated,issuetype,labels")
+    );
+    assert_eq!(backlog.query_param("maxResults").as_deref(), Some("50"));
+}
+
+#[tokio::test]
+async fn triage_bugs_searches_recent_open_bugs() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({
+            "issues": [{ "id": "9", "key": "PROJ-9", "fields": { "summary": "Crash on save" } }],
+            "isLast": true
+        })),
+    );
+
+    let messages = get(
+        &mut client,
+        "triage_bugs",
+        json!({ "project": "PROJ", "days": "14" }),
+    )
+    .await;
+    assert!(messages[0].contains("project PROJ during the last 14 days"));
+    assert!(messages[1].contains("Crash on save"));
+    let search = mock.only_request("POST", "/rest/api/3/search/jql").body;
+    assert_eq!(
+        search["jql"],
+        "(project = \"PROJ\" AND issuetype = Bug AND statusCategory != Done AND created >= -14d) ORDER BY created DESC"
+    );
+
+    // Missing or malformed arguments are invalid params
+    let response = client
+        .request(
+            "prompts/get",
+            json!({ "name": "triage_bugs", "arguments": {} }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+    let response = client
+        .request(
+            "prompts/get",
+            json!({ "name": "triage_bugs", "arguments": { "project": "PROJ", "days": "week" } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+    let response = client
+        .request("prompts/get", json!({ "name": "plan_roadmap" }))
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn sprint_review_picks_the_active_sprint() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board",
+        Reply::json(json!({ "values": [{ "id": 7 }] })),
+    );
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board/7/sprint",
+        Reply::json(json!({
+            "values": [
+                { "id": 11, "name": "Sprint 11", "state": "closed" },
+                { "id": 12, "name": "Sprint 12", "state": "active", "goal": "Ship exports" },
+                { "id": 13, "name": "Sprint 13", "state": "future" }
+            ]
+        })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({ "issues": [{ "key": "PROJ-5", "fields": {} }], "isLast": true })),
+    );
+
+    let messages = get(&mut client, "sprint_review", json!({ "project": "PROJ" })).await;
+    assert_eq!(messages.len(), 3);
+    assert!(messages[1].contains("Ship exports"));
+    assert!(messages[2].contains("PROJ-5"));
+    let search = mock.only_request("POST", "/rest/api/3/search/jql").body;
+    assert_eq!(search["jql"], "(sprint = 12) ORDER BY status, rank");
+
+    // A sprint can be chosen by name
+    get(
+        &mut client,
+        "sprint_review",
+        json!({ "project": "PROJ", "sprint": "sprint 11" }),
+    )
+    .await;
+    let searches = mock.requests_to("POST", "/rest/api/3/search/jql");
+    assert_eq!(
+        searches[1].body["jql"],
+        "(sprint = 11) ORDER BY status, rank"
+    );
+}
+
+#[tokio::test]
+async fn break_epic_embeds_the_epic_and_its_children() {
+    let primary = MockJira::start().await;
+    let secondary = MockJira::start().await;
+    let mut sites = BTreeMap::new();
+    sites.insert("primary".to_string(), site(&primary, Deployment::Cloud));
+    sites.insert(
+        "client".to_string(),
+        site(&secondary, Deployment::DataCenter),
+    );
+    let mut client = McpClient::connect(Jira::new(sites, "primary")).await;
+    secondary.on(
+        "GET",
+        "/rest/api/2/issue/CLI-1",
+        Reply::json(
+            json!({ "id": "1", "key": "CLI-1", "fields": { "summary": "Billing revamp" } }),
+        ),
+    );
+    secondary.on(
+        "POST",
+        "/rest/api/2/search",
+        Reply::json(json!({ "issues": [{ "key": "CLI-2", "fields": {} }], "total": 1 })),
+    );
+
+    let messages = get(
+        &mut client,
+        "break_epic",
+        json!({ "epic": "CLI-1", "workspace": "client" }),
+    )
+    .await;
+    assert!(messages[0].contains("`parentKey` set to CLI-1"));
+    assert!(messages[1].contains("Billing revamp"));
+    assert!(messages[2].contains("CLI-2"));
+    assert!(primary.requests().is_empty());
+    let search = secondary.only_request("POST", "/rest/api/2/search").body;
+    assert_eq!(search["jql"], "(parent = \"CLI-1\") ORDER BY rank");
+}
+
+#[tokio::test]
+async fn prompts_follow_the_tool_policy() {
+    let mock = MockJira::start().await;
+    let mut sites = BTreeMap::new();
+    sites.insert("primary".to_string(), site(&mock, Deployment::Cloud));
+    let policy = ToolPolicy {
+        deny: vec!["search_issues".to_string()],
+        ..Default::default()
+    };
+    let jira = Jira::new(sites, "primary")
+        .with_tool_policy(&policy)
+        .unwrap();
+    let mut client = McpClient::connect(jira).await;
+
+    let response = client.request("prompts/list", json!({})).await;
+    let names: Vec<_> = response["result"]["prompts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["groom_backlog"]);
+
+    let response = client
+        .request(
+            "prompts/get",
+            json!({ "name": "triage_bugs", "arguments": { "project": "PROJ" } }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(mock.requests().is_empty());
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use common::{McpClient, MockJira, Reply, cloud, site};
+use jira_rs::jira::Jira;
+use jira_rs::policy::ToolPolicy;
+use jira_rs::resources::JiraUri;
+use jira_rs::site::Deployment;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+async fn read(client: &mut McpClient, uri: &str) -> Value {
+    let response = client
+        .request("resources/read", json!({ "uri": uri }))
+        .await;
+    let contents = &response["result"]["contents"][0];
+    assert_eq!(contents["uri"], uri, "{}", response);
+    assert_eq!(contents["mimeType"], "application/json");
+    serde_json::from_str(contents["text"].as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn uris_are_parsed_with_an_optional_workspace() {
+    assert_eq!(
+        JiraUri::parse("jira://issue/PROJ-1"),
+        Some((JiraUri::Issue("PROJ-1".to_string()), None))
+    );
+    assert_eq!(
+        JiraUri::parse("jira://board/7/backlog?workspace=client"),
+        Some((JiraUri::BoardBacklog(7), Some("client".to_string())))
+    );
+    assert_eq!(
+        JiraUri::parse("jira://sprint/12").map(|(t, _)| t),
+        Some(JiraUri::Sprint(12))
+    );
+    assert_eq!(
+        JiraUri::parse("jira://filter/10000").map(|(t, _)| t),
+        Some(JiraUri::Filter(10000))
+    );
+    assert_eq!(JiraUri::parse("jira://sprint/abc"), None);
+    assert_eq!(JiraUri::parse("https://example.com/issue/PROJ-1"), None);
+}
+
+#[tokio::test]
+async fn templates_and_recent_issues_are_listed() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({
+            "issues": [
+                { "id": "1", "key": "PROJ-1", "fields": { "summary": "Login page" } },
+                { "id": "2", "key": "PROJ-2", "fields": { "summary": "Logout" } }
+            ],
+            "isLast": true
+        })),
+    );
+
+    let response = client.request("resources/templates/list", json!({})).await;
+    let templates: Vec<_> = response["result"]["resourceTemplates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["uriTemplate"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        templates,
+        [
+            "jira://issue/{key}",
+            "jira://sprint/{id}",
+            "jira://board/{id}/backlog",
+            "jira://filter/{id}"
+        ]
+    );
+
+    let response = client.request("resources/list", json!({})).await;
+    let resources = response["result"]["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 2);
+    assert_eq!(resources[0]["uri"], "jira://issue/PROJ-1");
+    assert_eq!(resources[0]["name"], "PROJ-1");
+    assert_eq!(resources[0]["title"], "Login page");
+
+    let search = mock.only_request("POST", "/rest/api/3/search/jql").body;
+    assert_eq!(
+        search["jql"],
+        "issue in issueHistory() ORDER BY lastViewed DESC"
+    );
+    assert_eq!(search["maxResults"], 20);
+}
+
+#[tokio::test]
+async fn issue_and_sprint_resources_are_read() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({
+            "id": "1",
+            "key": "PROJ-1",
+            "self": "https://example.atlassian.net/rest/api/3/issue/1",
+            "fields": { "summary": "Login page" }
+        })),
+    );
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/sprint/12",
+        Reply::json(json!({ "id": 12, "name": "Sprint 12", "state": "active" })),
+    );
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/sprint/12/issue",
+        Reply::json(json!({ "total": 1, "issues": [{ "id": "1", "key": "PROJ-1" }] })),
+    );
+
+    let issue = read(&mut client, "jira://issue/PROJ-1").await;
+    assert_eq!(issue["fields"]["summary"], "Login page");
+    let request = mock.only_request("GET", "/rest/api/3/issue/PROJ-1");
+    assert!(
+        request
+            .query_param("fields")
+            .unwrap()
+            .contains("description")
+    );
+
+    let sprint = read(&mut client, "jira://sprint/12").await;
+    assert_eq!(sprint["sprint"]["name"], "Sprint 12");
+    assert_eq!(sprint["issues"][0]["key"], "PROJ-1");
+    assert_eq!(sprint["total"], 1);
+}
+
+#[tokio::test]
+async fn backlog_and_filter_resources_are_read() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/agile/1.0/board/7/backlog",
+        Reply::json(json!({ "total": 1, "issues": [{ "key": "PROJ-5" }] })),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/3/filter/10000",
+        Reply::json(json!({ "id": "10000", "name": "My bugs", "jql": "type = Bug" })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({ "issues": [{ "key": "PROJ-9" }], "isLast": true })),
+    );
+
+    let backlog = read(&mut client, "jira://board/7/backlog").await;
+    assert_eq!(backlog["boardId"], 7);
+    assert_eq!(backlog["issues"][0]["key"], "PROJ-5");
+    let request = mock.only_request("GET", "/rest/agile/1.0/board/7/backlog");
+    assert_eq!(request.query_param("maxResults").as_deref(), Some("50"));
+
+    let filter = read(&mut client, "jira://filter/10000").await;
+    assert_eq!(
+        filter["filter"],
+        json!({ "id": "10000", "name": "My bugs", "jql": "type = Bug" })
+    );
+    assert_eq!(filter["issues"][0]["key"], "PROJ-9");
+    let search = mock.only_request("POST", "/rest/api/3/search/jql").body;
+    assert_eq!(search["jql"], "type = Bug");
+}
+
+#[tokio::test]
+async fn resources_honor_the_workspace_and_report_errors() {
+    let primary = MockJira::start().await;
+    let secondary = MockJira::start().await;
+    let mut sites = BTreeMap::new();
+    sites.insert("primary".to_string(), site(&primary, Deployment::Cloud));
+    sites.insert(
+        "client".to_string(),
+        site(&secondary, Deployment::DataCenter),
+    );
+    let mut client = McpClient::connect(Jira::new(sites, "primary")).await;
+    secondary.on(
+        "GET",
+        "/rest/api/2/issue/CLI-1",
+        Reply::json(json!({ "id": "1", "key": "CLI-1", "fields": {} })),
+    );
+
+    let issue = read(&mut client, "jira://issue/CLI-1?workspace=client").await;
+    assert_eq!(issue["key"], "CLI-1");
+    assert!(primary.requests().is_empty());
+
+    // Unknown URIs and missing issues are resource-not-found errors
+    let response = client
+        .request("resources/read", json!({ "uri": "jira://project/PROJ" }))
+        .await;
+    assert_eq!(response["error"]["code"], -32002);
+    let response = client
+        .request("resources/read", json!({ "uri": "jira://issue/PROJ-404" }))
+        .await;
+    assert_eq!(response["error"]["code"], -32002);
+    assert_eq!(response["error"]["data"]["error"]["status"], 404);
+}
+
+#[tokio::test]
+async fn resources_follow_the_tool_policy() {
+    let mock = MockJira::start().await;
+    let mut sites = BTreeMap::new();
+    sites.insert("primary".to_string(), site(&mock, Deployment::Cloud));
+    let policy = ToolPolicy {
+        deny: vec!["issue_get".to_string(), "search_issues".to_string()],
+        ..Default::default()
+    };
+    let jira = Jira::new(sites, "primary")
+        .with_tool_policy(&policy)
+        .unwrap();
+    let mut client = McpClient::connect(jira).await;
+
+    let response = client.request("resources/templates/list", json!({})).await;
+    let templates: Vec<_> = response["result"]["resourceTemplates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(templates, ["sprint", "board-backlog"]);
+
+    let response = client.request("resources/list", json!({})).await;
+    assert_eq!(response["result"]["resources"], json!([]));
+
+    for uri in ["jira://issue/PROJ-1", "jira://filter/10000"] {
+        let response = client
+            .request("resources/read", json!({ "uri": uri }))
+            .await;
+        assert_eq!(response["error"]["code"], -32602, "{}", response);
+    }
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn a_failed_search_lists_no_resources() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::status(500, json!({ "errorMessages": ["Internal error"] })),
+    );
+
+    let response = client.request("resources/list", json!({})).await;
+    assert_eq!(response["result"]["resources"], json!([]), "{}", response);
+}
@@ -0,0 +1,232 @@
+mod common;
+
+use common::{Reply, cloud, data_center, fields};
+use serde_json::json;
+
+#[tokio::test]
+async fn search_issues_composes_jql() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({ "issues": [{ "key": "PROJ-1" }], "isLast": true })),
+    );
+
+    let out = client
+        .call_ok(
+            "search_issues",
+            json!({
+                "text": "login",
+                "status": "Done",
+                "jql": "project = PROJ ORDER BY created DESC",
+                "limit": 10,
+                "filter": "basic"
+            }),
+        )
+        .await;
+    assert_eq!(out["issues"][0]["key"], "PROJ-1");
+
+    let body = mock.only_request("POST", "/rest/api/3/search/jql").body;
+    assert_eq!(
+        body["jql"],
+        "text ~ \"login\" AND status = \"Done\" AND (project = PROJ) ORDER BY created DESC"
+    );
+    assert_eq!(body["maxResults"], 10);
+    assert_eq!(body["fieldsByKeys"], true);
+    assert_eq!(body["fields"], json!(["id", "key", "summary", "status"]));
+    assert!(body.get("startAt").is_none());
+}
+
+#[tokio::test]
+async fn search_issues_resolves_assignee() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/myself",
+        Reply::json(json!({ "accountId": "acc-42" })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/search/jql",
+        Reply::json(json!({ "issues": [] })),
+    );
+
+    client
+        .call_ok("search_issues", json!({ "assignee": "me" }))
+        .await;
+    client
+        .call_ok(
+            "search_issues",
+            json!({ "assignee": "unassigned", "jql": "ORDER BY rank" }),
+        )
+        .await;
+
+    let searches = mock.requests_to("POST", "/rest/api/3/search/jql");
+    assert_eq!(searches[0].body["jql"], "assignee = \"acc-42\"");
+    assert_eq!(searches[1].body["jql"], "assignee is EMPTY ORDER BY rank");
+}
+
+#[tokio::test]
+async fn search_issues_pages_with_start_at_on_data_center() {
+    let (mock, mut client) = data_center().await;
+    mock.on(
+        "POST",
+        "/rest/api/2/search",
+        Reply::json(json!({ "issues": [], "total": 0 })),
+    );
+
+    client
+        .call_ok(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "startAt": 50 }),
+        )
+        .await;
+    let body = mock.only_request("POST", "/rest/api/2/search").body;
+    assert_eq!(body["jql"], "(project = PROJ)");
+    assert_eq!(body["startAt"], 50);
+}
+
+#[tokio::test]
+async fn search_issues_rejects_start_at_on_cloud() {
+    let (mock, mut client) = cloud().await;
+
+    let error = client
+        .call_err(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "startAt": 50 }),
+        )
+        .await;
+    assert_eq!(error["kind"], "validation");
+    assert!(error["message"].as_str().unwrap().contains("pageToken"));
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn search_issues_returns_a_page_token() {
+    let (mock, mut client) = cloud().await;
+    mock.on_fn("POST", "/rest/api/3/search/jql", |request| {
+        Reply::json(match request.body["nextPageToken"].as_str() {
+            None => json!({
+                "issues": [{ "key": "PROJ-1" }],
+                "nextPageToken": "page-2",
+                "isLast": false
+            }),
+            Some(_) => json!({ "issues": [{ "key": "PROJ-2" }], "isLast": true }),
+        })
+    });
+
+    let first = client
+        .call_ok("search_issues", json!({ "jql": "project = PROJ" }))
+        .await;
+    assert_eq!(
+        first,
+        json!({ "issues": [{ "key": "PROJ-1" }], "nextPageToken": "page-2", "isLast": false })
+    );
+    let second = client
+        .call_ok(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "pageToken": "page-2" }),
+        )
+        .await;
+    assert_eq!(
+        second,
+        json!({ "issues": [{ "key": "PROJ-2" }], "isLast": true })
+    );
+
+    let searches = mock.requests_to("POST", "/rest/api/3/search/jql");
+    assert!(searches[0].body.get("nextPageToken").is_none());
+    assert_eq!(searches[1].body["nextPageToken"], "page-2");
+}
+
+#[tokio::test]
+async fn search_issues_collects_all_pages_up_to_the_limit() {
+    let (mock, mut client) = data_center().await;
+    mock.on_fn("POST", "/rest/api/2/search", |request| {
+        let start = request.body["startAt"].as_u64().unwrap();
+        let size = request.body["maxResults"].as_u64().unwrap();
+        let issues: Vec<_> = (start..(start + size).min(250))
+            .map(|n| json!({ "key": format!("PROJ-{}", n) }))
+            .collect();
+        Reply::json(json!({ "issues": issues, "startAt": start, "total": 250 }))
+    });
+
+    let out = client
+        .call_ok(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "allPages": true }),
+        )
+        .await;
+    assert_eq!(out["issues"].as_array().unwrap().len(), 250);
+    assert_eq!(out["issues"][249]["key"], "PROJ-249");
+    assert_eq!(out["isLast"], true);
+    assert!(out.get("nextPageToken").is_none());
+    let starts: Vec<_> = mock
+        .requests_to("POST", "/rest/api/2/search")
+        .iter()
+        .map(|r| r.body["startAt"].clone())
+        .collect();
+    assert_eq!(starts, [json!(0), json!(100), json!(200)]);
+
+    // The limit caps the total and the token resumes after it
+    let out = client
+        .call_ok(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "allPages": true, "limit": 120 }),
+        )
+        .await;
+    assert_eq!(out["issues"].as_array().unwrap().len(), 120);
+    assert_eq!(out["nextPageToken"], "120");
+    assert_eq!(out["isLast"], false);
+    let searches = mock.requests_to("POST", "/rest/api/2/search");
+    assert_eq!(searches[4].body["maxResults"], 20);
+
+    let err = client
+        .call_err(
+            "search_issues",
+            json!({ "jql": "project = PROJ", "pageToken": "abc" }),
+        )
+        .await;
+    assert!(err.to_string().contains("Invalid page token"), "{}", err);
+}
+
+#[tokio::test]
+async fn fields_list_filters_custom_fields() {
+    let (mock, mut client) = cloud().await;
+    mock.on("GET", "/rest/api/3/field", Reply::json(fields()));
+
+    let out = client
+        .call_ok("fields_list", json!({ "fieldType": "custom" }))
+        .await;
+    assert_eq!(out["total"], 3);
+    assert_eq!(
+        out["fields"][0],
+        json!({ "id": "customfield_10016", "name": "Story Points", "type": "number", "custom": true })
+    );
+}
+
+#[tokio::test]
+async fn list_issue_types_and_project_users() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "GET",
+        "/rest/api/3/issuetype",
+        Reply::json(
+            json!([{ "id": "10001", "name": "Story", "description": "", "subtask": false }]),
+        ),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/3/user/assignable/search",
+        Reply::json(json!([{ "accountId": "acc-1", "displayName": "Ada" }])),
+    );
+
+    let types = client.call_ok("list_issue_types", json!({})).await;
+    assert_eq!(types["issueTypes"][0]["name"], "Story");
+
+    let users = client
+        .call_ok("project_get_users", json!({ "projectKey": "PROJ" }))
+        .await;
+    assert_eq!(users["users"][0]["displayName"], "Ada");
+    let request = mock.only_request("GET", "/rest/api/3/user/assignable/search");
+    assert_eq!(request.query_param("project").as_deref(), Some("PROJ"));
+}
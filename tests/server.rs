@@ -0,0 +1,147 @@
+mod common;
+
+use common::{McpClient, MockJira, Reply, cloud, site};
+use jira_rs::jira::Jira;
+use jira_rs::site::Deployment;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[tokio::test]
+async fn tools_list_exposes_every_tool_without_schema_keyword() {
+    let (_mock, mut client) = cloud().await;
+
+    let response = client.request("tools/list", json!({})).await;
+    let tools = response["result"]["tools"].as_array().unwrap();
+    let names: Vec<_> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+    for expected in [
+        "issue_create",
+        "issue_set_parent",
+        "search_issues",
+        "fields_list",
+        "sprint_update",
+        "workspace_list",
+        "cache_refresh",
+    ] {
+        assert!(names.contains(&expected), "missing tool {}", expected);
+    }
+    for tool in tools {
+        assert!(
+            tool["inputSchema"].get("$schema").is_none(),
+            "{}",
+            tool["name"]
+        );
+    }
+}
+
+#[tokio::test]
+async fn tool_calls_target_the_requested_workspace() {
+    let primary = MockJira::start().await;
+    let secondary = MockJira::start().await;
+    let mut sites = BTreeMap::new();
+    sites.insert("primary".to_string(), site(&primary, Deployment::Cloud));
+    sites.insert(
+        "client".to_string(),
+        site(&secondary, Deployment::DataCenter),
+    );
+    let mut client = McpClient::connect(Jira::new(sites, "primary")).await;
+
+    primary.on("DELETE", "/rest/api/3/issueLink/1", Reply::no_content());
+    secondary.on("DELETE", "/rest/api/2/issueLink/2", Reply::no_content());
+
+    client
+        .call_ok("issue_delete_link", json!({ "linkId": "1" }))
+        .await;
+    client
+        .call_ok(
+            "issue_delete_link",
+            json!({ "linkId": "2", "workspace": "client" }),
+        )
+        .await;
+    assert_eq!(primary.requests().len(), 1);
+    assert_eq!(secondary.requests().len(), 1);
+
+    let workspaces = client.call_ok("workspace_list", json!({})).await;
+    assert_eq!(
+        workspaces,
+        json!({
+            "workspaces": [
+                { "name": "client", "baseUrl": secondary.url(), "deployment": "datacenter", "default": false },
+                { "name": "primary", "baseUrl": primary.url(), "deployment": "cloud", "default": true }
+            ]
+        })
+    );
+
+    let error = client
+        .call_err(
+            "issue_delete_link",
+            json!({ "linkId": "3", "workspace": "staging" }),
+        )
+        .await;
+    assert_eq!(error["kind"], "not_found");
+    assert_eq!(error["resource"], "Workspace");
+}
+
+#[tokio::test]
+async fn requests_send_basic_auth() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "DELETE",
+        "/rest/api/3/issue/PROJ-1/comment/9",
+        Reply::no_content(),
+    );
+
+    client
+        .call_ok(
+            "issue_delete_comment",
+            json!({ "issueKey": "PROJ-1", "commentId": "9" }),
+        )
+        .await;
+    let request = mock.only_request("DELETE", "/rest/api/3/issue/PROJ-1/comment/9");
+    // base64("bot@example.com:secret")
+    assert_eq!(
+        request.header("authorization"),
+        Some("Basic Ym90QGV4YW1wbGUuY29tOnNlY3JldA==")
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried() {
+    let (mock, mut client) = cloud().await;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    mock.on_fn("GET", "/rest/api/3/issue/PROJ-1", move |_| {
+        if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+            Reply::status(429, json!({ "errorMessages": ["Rate limit exceeded"] }))
+                .with_header("Retry-After", "0")
+        } else {
+            Reply::json(json!({ "id": "1", "key": "PROJ-1", "self": "", "fields": {} }))
+        }
+    });
+
+    let issue = client
+        .call_ok("issue_get", json!({ "issueKey": "PROJ-1" }))
+        .await;
+    assert_eq!(issue["key"], "PROJ-1");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn non_idempotent_requests_are_not_retried() {
+    let (mock, mut client) = cloud().await;
+    mock.on(
+        "POST",
+        "/rest/agile/1.0/sprint/1/issue",
+        Reply::status(503, json!({ "errorMessages": ["Service unavailable"] })),
+    );
+
+    let error = client
+        .call_err(
+            "sprint_add_issues",
+            json!({ "sprintId": 1, "issueKeys": ["PROJ-1"] }),
+        )
+        .await;
+    assert_eq!(error["status"], 503);
+    assert_eq!(mock.requests().len(), 1);
+}
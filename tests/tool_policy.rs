@@ -0,0 +1,127 @@
+mod common;
+
+use common::{McpClient, MockJira, site};
+use jira_rs::jira::Jira;
+use jira_rs::policy::ToolPolicy;
+use jira_rs::site::Deployment;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn jira(mock: &MockJira) -> Jira {
+    let mut sites = BTreeMap::new();
+    sites.insert("default".to_string(), site(mock, Deployment::Cloud));
+    Jira::new(sites, "default")
+}
+
+fn patterns(list: &[&str]) -> Vec<String> {
+    list.iter().map(|p| p.to_string()).collect()
+}
+
+async fn tool_names(client: &mut McpClient) -> Vec<String> {
+    let response = client.request("tools/list", json!({})).await;
+    let mut names: Vec<String> = response["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn read_only_mode_strips_mutating_tools() {
+    let mock = MockJira::start().await;
+    let policy = ToolPolicy {
+        read_only: true,
+        ..Default::default()
+    };
+    let mut client = McpClient::connect(jira(&mock).with_tool_policy(&policy).unwrap()).await;
+
+    assert_eq!(
+        tool_names(&mut client).await,
+        [
+            "attachment_read",
+            "audit_log_query",
+            "board_get_backlog",
+            "board_get_sprints",
+            "cache_refresh",
+            "fields_list",
+            "issue_get",
+            "issue_list_attachments",
+            "list_issue_types",
+            "project_get_users",
+            "search_issues",
+            "workspace_list",
+        ]
+    );
+
+    // Stripped tools cannot be called by name either
+    let response = client
+        .request(
+            "tools/call",
+            json!({ "name": "issue_delete", "arguments": { "issueKey": "PROJ-1" } }),
+        )
+        .await;
+    assert!(response.get("error").is_some(), "{}", response);
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn deny_list_removes_destructive_tools_and_globs() {
+    let mock = MockJira::start().await;
+    let policy = ToolPolicy {
+        deny: patterns(&["destructive", "sprint_*"]),
+        ..Default::default()
+    };
+    let mut client = McpClient::connect(jira(&mock).with_tool_policy(&policy).unwrap()).await;
+
+    let names = tool_names(&mut client).await;
+    for removed in [
+        "issue_delete",
+        "issue_archive",
+        "issue_delete_comment",
+        "issue_delete_link",
+        "sprint_create",
+        "sprint_delete",
+    ] {
+        assert!(
+            !names.iter().any(|n| n == removed),
+            "{} still listed",
+            removed
+        );
+    }
+    for kept in ["issue_create", "issue_unarchive", "board_get_sprints"] {
+        assert!(names.iter().any(|n| n == kept), "{} missing", kept);
+    }
+}
+
+#[tokio::test]
+async fn allow_list_keeps_only_matching_tools() {
+    let mock = MockJira::start().await;
+    let policy = ToolPolicy {
+        allow: patterns(&["read", "issue_add_comment"]),
+        deny: patterns(&["board_*"]),
+        ..Default::default()
+    };
+    let mut client = McpClient::connect(jira(&mock).with_tool_policy(&policy).unwrap()).await;
+
+    let names = tool_names(&mut client).await;
+    assert!(names.iter().any(|n| n == "issue_add_comment"));
+    assert!(names.iter().any(|n| n == "search_issues"));
+    assert!(!names.iter().any(|n| n == "issue_create"));
+    // Deny wins over allow
+    assert!(!names.iter().any(|n| n.starts_with("board_")));
+}
+
+#[tokio::test]
+async fn unknown_patterns_are_rejected() {
+    let mock = MockJira::start().await;
+    let policy = ToolPolicy {
+        deny: patterns(&["issue_delet"]),
+        ..Default::default()
+    };
+
+    let error = jira(&mock).with_tool_policy(&policy).unwrap_err();
+    assert!(error.to_string().contains("issue_delet"), "{}", error);
+}
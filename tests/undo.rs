@@ -0,0 +1,204 @@
+mod common;
+
+use common::{MockJira, Reply, cloud};
+use serde_json::json;
+
+fn stub_issue(mock: &MockJira, fields: serde_json::Value) {
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1",
+        Reply::json(json!({ "id": "1", "key": "PROJ-1", "fields": fields })),
+    );
+    mock.on("PUT", "/rest/api/3/issue/PROJ-1", Reply::no_content());
+}
+
+#[tokio::test]
+async fn undo_last_restores_edited_fields() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(
+        &mock,
+        json!({
+            "summary": "Old summary",
+            "priority": { "id": "3", "name": "Medium" },
+            "labels": ["backend"]
+        }),
+    );
+
+    let out = client
+        .call_ok(
+            "issue_edit_details",
+            json!({ "issueKey": "PROJ-1", "summary": "New summary", "priority": "High", "labels": [] }),
+        )
+        .await;
+    assert_eq!(out["undoId"], 1);
+
+    // Only the edited fields are snapshotted
+    let snapshot = mock.only_request("GET", "/rest/api/3/issue/PROJ-1");
+    let mut fields: Vec<_> = snapshot
+        .query_param("fields")
+        .unwrap()
+        .split(',')
+        .map(str::to_string)
+        .collect();
+    fields.sort();
+    assert_eq!(fields, ["labels", "priority", "summary"]);
+
+    let out = client.call_ok("undo_last", json!({})).await;
+    assert_eq!(out["operation"]["tool"], "issue_edit_details");
+    assert_eq!(out["operation"]["undone"], true);
+    let puts = mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1");
+    assert_eq!(
+        puts[1].body,
+        json!({
+            "fields": {
+                "summary": "Old summary",
+                "priority": { "id": "3" },
+                "labels": ["backend"]
+            }
+        })
+    );
+
+    // Nothing left to undo
+    let error = client.call_err("undo_last", json!({})).await;
+    assert_eq!(error["kind"], "not_found");
+}
+
+#[tokio::test]
+async fn undo_operation_restores_the_previous_assignee_once() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(&mock, json!({ "assignee": { "accountId": "acc-old" } }));
+    mock.on(
+        "PUT",
+        "/rest/api/3/issue/PROJ-1/assignee",
+        Reply::no_content(),
+    );
+
+    let out = client
+        .call_ok(
+            "issue_assign",
+            json!({ "issueKey": "PROJ-1", "assignee": "acc-new" }),
+        )
+        .await;
+    let id = out["undoId"].clone();
+
+    client
+        .call_ok("undo_operation", json!({ "operationId": id }))
+        .await;
+    let puts = mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1/assignee");
+    assert_eq!(puts[0].body, json!({ "accountId": "acc-new" }));
+    assert_eq!(puts[1].body, json!({ "accountId": "acc-old" }));
+
+    let error = client
+        .call_err("undo_operation", json!({ "operationId": id }))
+        .await;
+    assert_eq!(error["kind"], "validation");
+    let error = client
+        .call_err("undo_operation", json!({ "operationId": 99 }))
+        .await;
+    assert_eq!(error["kind"], "not_found");
+}
+
+#[tokio::test]
+async fn undo_reverses_a_transition_when_the_workflow_allows() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(
+        &mock,
+        json!({ "status": { "id": "10000", "name": "To Do" } }),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1/transitions",
+        Reply::json(json!({
+            "transitions": [
+                { "id": "11", "name": "Reopen", "to": { "id": "10000", "name": "To Do" } },
+                { "id": "21", "name": "Start", "to": { "id": "3", "name": "In Progress" } }
+            ]
+        })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/transitions",
+        Reply::no_content(),
+    );
+
+    client
+        .call_ok(
+            "issue_update_status",
+            json!({ "issueKey": "PROJ-1", "status": "In Progress" }),
+        )
+        .await;
+    client
+        .call_ok("undo_last", json!({ "issueKey": "PROJ-1" }))
+        .await;
+
+    let posted = mock.requests_to("POST", "/rest/api/3/issue/PROJ-1/transitions");
+    assert_eq!(posted[0].body, json!({ "transition": { "id": "21" } }));
+    assert_eq!(posted[1].body, json!({ "transition": { "id": "11" } }));
+}
+
+#[tokio::test]
+async fn undo_fails_without_a_transition_back() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(
+        &mock,
+        json!({ "status": { "id": "10000", "name": "Backlog" } }),
+    );
+    mock.on(
+        "GET",
+        "/rest/api/3/issue/PROJ-1/transitions",
+        Reply::json(json!({
+            "transitions": [{ "id": "31", "name": "Finish", "to": { "id": "5", "name": "Done" } }]
+        })),
+    );
+    mock.on(
+        "POST",
+        "/rest/api/3/issue/PROJ-1/transitions",
+        Reply::no_content(),
+    );
+
+    let out = client
+        .call_ok(
+            "issue_update_status",
+            json!({ "issueKey": "PROJ-1", "status": "Done" }),
+        )
+        .await;
+    let error = client.call_err("undo_last", json!({})).await;
+    assert_eq!(error["kind"], "not_found");
+    assert_eq!(error["name"], "Backlog");
+    assert_eq!(
+        mock.requests_to("POST", "/rest/api/3/issue/PROJ-1/transitions")
+            .len(),
+        1
+    );
+
+    // The operation stays in the journal
+    let error = client
+        .call_err("undo_operation", json!({ "operationId": out["undoId"] }))
+        .await;
+    assert_eq!(error["kind"], "not_found");
+    assert_eq!(error["resource"], "Transition");
+}
+
+#[tokio::test]
+async fn undo_restores_a_removed_parent() {
+    let (mock, mut client) = cloud().await;
+    stub_issue(
+        &mock,
+        json!({ "parent": { "id": "100", "key": "PROJ-100", "fields": { "summary": "Epic" } } }),
+    );
+
+    client
+        .call_ok(
+            "issue_set_parent",
+            json!({ "issueKey": "PROJ-1", "parentKey": "" }),
+        )
+        .await;
+    client.call_ok("undo_last", json!({})).await;
+
+    let puts = mock.requests_to("PUT", "/rest/api/3/issue/PROJ-1");
+    assert_eq!(puts[0].body, json!({ "fields": { "parent": null } }));
+    assert_eq!(
+        puts[1].body,
+        json!({ "fields": { "parent": { "key": "PROJ-100" } } })
+    );
+}